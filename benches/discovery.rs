@@ -0,0 +1,115 @@
+//! Benchmarks of the discovery on the synthetic sample logs, run with `cargo bench`.
+//!
+//! The `existential` group compares the co-occurrence counts with scanning every trace for
+//! every pair of activities, as the existential analysis did before activities were interned.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use matrix_discovery::{
+    dependency_types::existential::{
+        check_existential_dependency_in_co_occurrences, CoOccurrences,
+    },
+    generate_adj_matrix_from_traces,
+    parser::XesLog,
+    variants::Variants,
+};
+use std::hint::black_box;
+
+/// Repetitions of the traces of a log for the large version of it.
+const SCALE: usize = 100;
+
+fn load(path: &str) -> Vec<Vec<String>> {
+    XesLog::from_path(path)
+        .and_then(|log| log.traces())
+        .unwrap_or_else(|e| panic!("cannot load {path}: {e}"))
+}
+
+/// The sample logs, and the first one repeated `SCALE` times.
+fn logs() -> Vec<(String, Vec<Vec<String>>)> {
+    let log = load("sample-data/synthetic-log/event_log_08.xes");
+    let noise = load("sample-data/synthetic-logs-noise/event_log_noise_08.xes");
+    let scaled = std::iter::repeat_n(&log, SCALE)
+        .flatten()
+        .cloned()
+        .collect();
+    vec![
+        ("L08".to_string(), log),
+        ("L08 noise".to_string(), noise),
+        (format!("L08 x{SCALE}"), scaled),
+    ]
+}
+
+fn as_str(traces: &[Vec<String>]) -> Vec<Vec<&str>> {
+    traces
+        .iter()
+        .map(|trace| trace.iter().map(String::as_str).collect())
+        .collect()
+}
+
+fn activities<'a>(traces: &[Vec<&'a str>]) -> Vec<&'a str> {
+    let mut activities: Vec<&str> = traces.iter().flatten().copied().collect();
+    activities.sort();
+    activities.dedup();
+    activities
+}
+
+/// The implication and negated equivalence ratios of every pair by scanning the traces.
+fn scan_traces(activities: &[&str], traces: &[Vec<&str>]) -> usize {
+    let mut holds = 0;
+    for from in activities {
+        for to in activities {
+            let implication = traces
+                .iter()
+                .filter(|trace| !trace.contains(from) || trace.contains(to))
+                .count();
+            let negated_equivalence = traces
+                .iter()
+                .filter(|trace| trace.contains(from) != trace.contains(to))
+                .count();
+            holds += usize::from(implication == traces.len()) + negated_equivalence;
+        }
+    }
+    holds
+}
+
+fn co_occurrences(activities: &[&str], traces: &[Vec<&str>]) -> usize {
+    let co_occurrences = CoOccurrences::new(&Variants::from_traces(traces));
+    activities
+        .iter()
+        .flat_map(|from| activities.iter().map(move |to| (from, to)))
+        .filter(|(from, to)| {
+            check_existential_dependency_in_co_occurrences(from, to, &co_occurrences, 1.0).is_some()
+        })
+        .count()
+}
+
+fn existential(c: &mut Criterion) {
+    let mut group = c.benchmark_group("existential");
+    group.sample_size(10);
+    for (name, traces) in logs() {
+        let traces = as_str(&traces);
+        let activities = activities(&traces);
+        group.bench_with_input(BenchmarkId::new("scan traces", &name), &traces, |b, t| {
+            b.iter(|| scan_traces(black_box(&activities), black_box(t)))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("co-occurrences", &name),
+            &traces,
+            |b, t| b.iter(|| co_occurrences(black_box(&activities), black_box(t))),
+        );
+    }
+    group.finish();
+}
+
+fn matrix(c: &mut Criterion) {
+    let mut group = c.benchmark_group("matrix");
+    group.sample_size(10);
+    for (name, traces) in logs() {
+        group.bench_with_input(BenchmarkId::from_parameter(&name), &traces, |b, t| {
+            b.iter(|| generate_adj_matrix_from_traces(black_box(t.clone()), 1.0, 1.0, 1.0))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, existential, matrix);
+criterion_main!(benches);
@@ -0,0 +1,479 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Args, Parser, Subcommand};
+use matrix_discovery::{
+    dependency_types::dependency::Dependency,
+    evaluation::{
+        log_number, render_accuracy_table,
+        sweep::{format_metric, threshold_grid, SweepMetric, ThresholdSweep},
+        EvaluationSet, LogAccuracy,
+    },
+    generate_adj_matrix_from_traces,
+    ground_truth::{load_ground_truth, GroundTruthError},
+    matrix::MatrixFormat,
+    parser::{
+        csv::CsvOptions, ocel::OcelLog, Classifier, LifecyclePolicy, ParseError, ParseOptions,
+        TimestampPolicy, XesLog,
+    },
+};
+
+/// Discovers adjacency matrices of temporal and existential dependencies from event logs.
+#[derive(Parser)]
+#[command(name = "matrix-discovery", version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Discover the adjacency matrix of an XES, CSV or OCEL 2.0 event log
+    Discover(DiscoverArgs),
+    /// Evaluate the discovery against the ground truth of the synthetic event logs
+    Evaluate(EvaluateArgs),
+    /// Evaluate the discovery over a grid of temporal and existential thresholds
+    Sweep(SweepArgs),
+}
+
+#[derive(Args)]
+struct DiscoverArgs {
+    /// Path to the XES event log, to a CSV event log with one event per row, or to an OCEL 2.0
+    /// event log (`.jsonocel`, `.xmlocel` or `.json`)
+    log: PathBuf,
+
+    /// Minimum ratio of occurrences that must agree on the direction of a temporal dependency
+    #[arg(short, long, default_value_t = 1.0, value_parser = parse_threshold)]
+    temporal_threshold: f64,
+
+    /// Minimum ratio of traces that must satisfy an existential dependency
+    #[arg(short, long, default_value_t = 1.0, value_parser = parse_threshold)]
+    existential_threshold: f64,
+
+    /// Minimum ratio of occurrences that must directly follow each other for a direct temporal
+    /// dependency
+    #[arg(short, long, default_value_t = 1.0, value_parser = parse_threshold)]
+    direct_threshold: f64,
+
+    /// Name of a classifier declared in the log to use as activity instead of `concept:name`
+    #[arg(short, long, conflicts_with = "keys")]
+    classifier: Option<String>,
+
+    /// Event attribute keys whose values form the activity, e.g. `concept:name,org:resource`
+    #[arg(long, value_delimiter = ',')]
+    keys: Vec<String>,
+
+    /// Which events to use based on their `lifecycle:transition`
+    #[arg(long, value_enum, default_value_t = LifecyclePolicy::Complete)]
+    lifecycle: LifecyclePolicy,
+
+    /// How to order the events of a trace
+    #[arg(long, value_enum, default_value_t = TimestampPolicy::Sort)]
+    timestamps: TimestampPolicy,
+
+    /// Event attribute ordering events with the same timestamp, instead of their order in the log
+    #[arg(long)]
+    tie_break: Option<String>,
+
+    /// Output format of the matrix
+    #[arg(short, long, value_enum, default_value_t = MatrixFormat::Text)]
+    format: MatrixFormat,
+
+    /// Follow every dependency with the confidence of its temporal and existential part
+    #[arg(long)]
+    scores: bool,
+
+    /// Write the matrix to this file instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    #[command(flatten)]
+    csv: CsvArgs,
+
+    /// Object type to flatten an OCEL 2.0 log on, instead of one matrix per object type
+    #[arg(long)]
+    object_type: Option<String>,
+}
+
+/// Format of CSV event logs, guessed from their header line unless given
+#[derive(Args)]
+#[command(next_help_heading = "CSV event logs")]
+struct CsvArgs {
+    /// Delimiter of the fields
+    #[arg(long)]
+    delimiter: Option<char>,
+
+    /// Column of the case id
+    #[arg(long)]
+    case_column: Option<String>,
+
+    /// Column of the activity name
+    #[arg(long)]
+    activity_column: Option<String>,
+
+    /// Column of the timestamp of the events
+    #[arg(long)]
+    timestamp_column: Option<String>,
+
+    /// Format of the timestamps, e.g. `%d.%m.%Y %H:%M`
+    #[arg(long)]
+    timestamp_format: Option<String>,
+}
+
+#[derive(Args)]
+struct EvaluateArgs {
+    /// Directories containing the numbered event logs, e.g. `event_log_03.xes`
+    #[arg(default_values = ["sample-data/synthetic-log", "sample-data/synthetic-logs-noise"])]
+    log_dirs: Vec<PathBuf>,
+
+    /// Directory containing the ground truth of every log, e.g. `deps_03.txt`
+    #[arg(short, long, default_value = "sample-data/ground-truth")]
+    ground_truth: PathBuf,
+
+    /// Minimum ratio of occurrences that must agree on the direction of a temporal dependency
+    #[arg(short, long, default_value_t = 1.0, value_parser = parse_threshold)]
+    temporal_threshold: f64,
+
+    /// Minimum ratio of traces that must satisfy an existential dependency
+    #[arg(short, long, default_value_t = 1.0, value_parser = parse_threshold)]
+    existential_threshold: f64,
+
+    /// Minimum ratio of occurrences that must directly follow each other for a direct temporal
+    /// dependency
+    #[arg(short, long, default_value_t = 1.0, value_parser = parse_threshold)]
+    direct_threshold: f64,
+
+    /// Also print the confusion matrices and per-class precision, recall and F1 of every set
+    #[arg(long)]
+    per_class: bool,
+
+    /// Output format of the tables
+    #[arg(short, long, value_enum, default_value_t = MatrixFormat::Text)]
+    format: MatrixFormat,
+
+    /// Write the tables to this file instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct SweepArgs {
+    /// Directories containing the numbered event logs, e.g. `event_log_noise_03.xes`
+    #[arg(default_values = ["sample-data/synthetic-logs-noise"])]
+    log_dirs: Vec<PathBuf>,
+
+    /// Directory containing the ground truth of every log, e.g. `deps_03.txt`
+    #[arg(short, long, default_value = "sample-data/ground-truth")]
+    ground_truth: PathBuf,
+
+    /// Lowest threshold of the grid
+    #[arg(long, default_value_t = 0.5, value_parser = parse_threshold)]
+    min: f64,
+
+    /// Highest threshold of the grid
+    #[arg(long, default_value_t = 1.0, value_parser = parse_threshold)]
+    max: f64,
+
+    /// Distance between two thresholds of the grid
+    #[arg(long, default_value_t = 0.05, value_parser = parse_step)]
+    step: f64,
+
+    /// Minimum ratio of occurrences that must directly follow each other for a direct temporal
+    /// dependency
+    #[arg(short, long, default_value_t = 1.0, value_parser = parse_threshold)]
+    direct_threshold: f64,
+
+    /// Output format of the grid
+    #[arg(short, long, value_enum, default_value_t = MatrixFormat::Text)]
+    format: MatrixFormat,
+
+    /// Write the grid to this file instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, thiserror::Error)]
+enum CliError {
+    #[error("Error parsing {0}: {1}")]
+    Parse(String, ParseError),
+    #[error("Error reading {0}: {1}")]
+    Read(String, std::io::Error),
+    #[error("Invalid ground truth {0}: {1}")]
+    GroundTruth(String, GroundTruthError),
+    #[error("Error writing {0}: {1}")]
+    Write(String, std::io::Error),
+    #[error("{0}")]
+    InvalidArgument(String),
+}
+
+fn parse_threshold(value: &str) -> Result<f64, String> {
+    let threshold: f64 = value
+        .parse()
+        .map_err(|_| format!("`{value}` is not a number"))?;
+    if (0.0..=1.0).contains(&threshold) {
+        Ok(threshold)
+    } else {
+        Err("threshold must be between 0 and 1".to_string())
+    }
+}
+
+fn discover(args: DiscoverArgs) -> Result<(), CliError> {
+    let classifier = match (args.classifier, args.keys) {
+        (Some(name), _) => Classifier::Declared(name),
+        (None, keys) if !keys.is_empty() => Classifier::Keys(keys),
+        _ => Classifier::Name,
+    };
+    let log = args.log.to_string_lossy();
+    let options = ParseOptions {
+        classifier,
+        lifecycle: args.lifecycle,
+        timestamps: args.timestamps,
+        tie_break: args.tie_break,
+    };
+    let extension = args
+        .log
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase());
+    let parse_error = |e| CliError::Parse(log.to_string(), e);
+
+    // an OCEL log is flattened on every object type, each giving its own matrix
+    let xes_logs = match extension.as_deref() {
+        Some("jsonocel" | "xmlocel" | "json") => {
+            let ocel = OcelLog::from_path(&args.log).map_err(parse_error)?;
+            let object_types = match args.object_type {
+                Some(object_type) => vec![object_type],
+                None => ocel.object_types(),
+            };
+            object_types
+                .into_iter()
+                .map(|object_type| {
+                    let xes = ocel.flatten(&object_type)?;
+                    Ok((Some(object_type), xes))
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(parse_error)?
+        }
+        Some("csv") => {
+            let bytes = fs::read(&args.log).map_err(|e| CliError::Read(log.to_string(), e))?;
+            let xes = CsvOptions::detect(&bytes, args.csv.delimiter)
+                .map(|detected| CsvOptions {
+                    case_column: args.csv.case_column.unwrap_or(detected.case_column),
+                    activity_column: args.csv.activity_column.unwrap_or(detected.activity_column),
+                    timestamp_column: args.csv.timestamp_column.or(detected.timestamp_column),
+                    timestamp_format: args.csv.timestamp_format,
+                    ..detected
+                })
+                .and_then(|csv_options| XesLog::from_csv(&bytes, &csv_options))
+                .map_err(parse_error)?;
+            vec![(None, xes)]
+        }
+        _ => vec![(None, XesLog::from_path(&args.log).map_err(parse_error)?)],
+    };
+
+    let mut output = String::new();
+    for (object_type, xes) in xes_logs {
+        let parsed = xes.parse(&options).map_err(parse_error)?;
+        if let Some(object_type) = &object_type {
+            eprintln!("Object type: {object_type}");
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            let title = format!("Object type {object_type}");
+            let heading = match args.format {
+                MatrixFormat::Text | MatrixFormat::Csv => format!("{title}\n\n"),
+                MatrixFormat::Markdown => format!("### {title}\n\n"),
+            };
+            output.push_str(&heading);
+        }
+        eprintln!("{}", parsed.report);
+
+        let matrix = generate_adj_matrix_from_traces(
+            parsed.traces,
+            args.existential_threshold,
+            args.temporal_threshold,
+            args.direct_threshold,
+        );
+        if args.scores {
+            output.push_str(&matrix.render_with_scores(args.format));
+        } else {
+            output.push_str(&matrix.render(args.format));
+        }
+    }
+    write_output(args.output.as_ref(), &output)
+}
+
+fn evaluate(args: EvaluateArgs) -> Result<(), CliError> {
+    let sets = args
+        .log_dirs
+        .iter()
+        .map(|dir| {
+            evaluate_dir(
+                dir,
+                &args.ground_truth,
+                args.existential_threshold,
+                args.temporal_threshold,
+                args.direct_threshold,
+            )
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut output = render_accuracy_table(&sets, args.format);
+    if args.per_class {
+        for set in &sets {
+            let title = format!("Per-class evaluation of {}", set.name);
+            let heading = match args.format {
+                MatrixFormat::Text | MatrixFormat::Csv => format!("\n{}\n\n", title),
+                MatrixFormat::Markdown => format!("\n### {}\n\n", title),
+            };
+            output.push_str(&heading);
+            output.push_str(&set.total().report.render(args.format));
+        }
+    }
+
+    write_output(args.output.as_ref(), &output)
+}
+
+/// Evaluates every XES log in `dir` that has a ground truth in `ground_truth_dir`, in file name order.
+fn evaluate_dir(
+    dir: &Path,
+    ground_truth_dir: &Path,
+    existential_threshold: f64,
+    temporal_threshold: f64,
+    direct_threshold: f64,
+) -> Result<EvaluationSet, CliError> {
+    let logs = load_logs(dir, ground_truth_dir)?
+        .into_iter()
+        .map(|(number, deps, traces)| {
+            LogAccuracy::evaluate(
+                &format!("L{:02}", number),
+                &deps,
+                traces,
+                existential_threshold,
+                temporal_threshold,
+                direct_threshold,
+            )
+        })
+        .collect();
+
+    Ok(EvaluationSet {
+        name: dir
+            .file_name()
+            .unwrap_or(dir.as_os_str())
+            .to_string_lossy()
+            .to_string(),
+        logs,
+    })
+}
+
+/// A synthetic log as `(log number, ground truth, traces)`.
+type LabelledLog = (usize, Vec<Dependency>, Vec<Vec<String>>);
+
+/// Loads every XES log in `dir` that has a ground truth in `ground_truth_dir`, in file name
+/// order.
+fn load_logs(dir: &Path, ground_truth_dir: &Path) -> Result<Vec<LabelledLog>, CliError> {
+    let read_error = |e| CliError::Read(dir.to_string_lossy().to_string(), e);
+
+    let mut paths = fs::read_dir(dir)
+        .map_err(read_error)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(read_error)?;
+    paths.retain(|path| path.extension().is_some_and(|ext| ext == "xes"));
+    paths.sort();
+
+    let mut logs = Vec::new();
+    for path in paths {
+        let name = path.file_stem().unwrap_or_default().to_string_lossy();
+        let ground_truth = log_number(&name).map(|number| {
+            (
+                number,
+                ground_truth_dir.join(format!("deps_{:02}.txt", number)),
+            )
+        });
+        let Some((number, ground_truth)) = ground_truth.filter(|(_, path)| path.is_file()) else {
+            eprintln!("Skipping {}: no ground truth", path.display());
+            continue;
+        };
+        let deps = load_ground_truth(&ground_truth)
+            .map_err(|e| CliError::GroundTruth(ground_truth.to_string_lossy().to_string(), e))?;
+
+        let log = path.to_string_lossy();
+        let traces = XesLog::from_path(&path)
+            .and_then(|xes| xes.traces())
+            .map_err(|e| CliError::Parse(log.to_string(), e))?;
+        logs.push((number, deps, traces));
+    }
+
+    Ok(logs)
+}
+
+fn sweep(args: SweepArgs) -> Result<(), CliError> {
+    if args.min > args.max {
+        return Err(CliError::InvalidArgument(
+            "--min must not be greater than --max".to_string(),
+        ));
+    }
+
+    let mut logs = Vec::new();
+    for dir in &args.log_dirs {
+        logs.extend(
+            load_logs(dir, &args.ground_truth)?
+                .into_iter()
+                .map(|(_, deps, traces)| (deps, traces)),
+        );
+    }
+
+    let thresholds = threshold_grid(args.min, args.max, args.step);
+    let sweep = ThresholdSweep::run(&logs, &thresholds, &thresholds, args.direct_threshold);
+
+    let mut output = sweep.render(args.format);
+    if let (Some(best), MatrixFormat::Text | MatrixFormat::Markdown) = (sweep.best(), args.format) {
+        output.push_str(&format!(
+            "\nBest: temporal threshold {:.2}, existential threshold {:.2} (accuracy {})\n",
+            best.temporal_threshold,
+            best.existential_threshold,
+            format_metric(SweepMetric::Accuracy.value(best)),
+        ));
+    }
+
+    write_output(args.output.as_ref(), &output)
+}
+
+fn parse_step(value: &str) -> Result<f64, String> {
+    match parse_threshold(value)? {
+        step if step > 0.0 => Ok(step),
+        _ => Err("step must be greater than 0".to_string()),
+    }
+}
+
+fn write_output(output: Option<&PathBuf>, content: &str) -> Result<(), CliError> {
+    match output {
+        Some(path) => fs::write(path, content)
+            .map_err(|e| CliError::Write(path.to_string_lossy().to_string(), e)),
+        None => {
+            print!("{content}");
+            Ok(())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match cli.command {
+        Command::Discover(args) => discover(args),
+        Command::Evaluate(args) => evaluate(args),
+        Command::Sweep(args) => sweep(args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::FAILURE
+        }
+    }
+}
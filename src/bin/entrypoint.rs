@@ -1,11 +1,24 @@
 use matrix_discovery::{evaluation::Evaluation, routes::Route};
 use matrix_discovery::{
-    generate_adj_matrix_from_traces, generate_xes,
-    parser::{parse_into_traces, variants_of_traces},
+    generate_xes,
+    matrix::AdjacencyMatrix,
+    matrix_table::{MatrixLegend, MatrixTable},
+    parser::{
+        csv::{csv_header, CsvOptions, DELIMITERS},
+        AvailableClassifiers, Classifier, LifecyclePolicy, ParseError, ParseOptions, ParseReport,
+        TimestampPolicy,
+    },
+    worker::{
+        DiscoveredMatrix, DiscoveryInput, DiscoveryOptions, DiscoveryOutput, DiscoveryProgress,
+        DiscoveryWorker, LogSource,
+    },
 };
 use wasm_bindgen::{closure::Closure, JsCast, JsValue, UnwrapThrowExt};
-use web_sys::{File, FileReader, HtmlAnchorElement, HtmlInputElement, HtmlTextAreaElement};
+use web_sys::{
+    File, FileReader, HtmlAnchorElement, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement,
+};
 use yew::prelude::*;
+use yew_agent::{Bridge, Bridged};
 use yew_router::prelude::*;
 
 struct Main;
@@ -18,6 +31,8 @@ impl Component for Main {
         Self
     }
 
+    // `html!` expands the generic `Switch<Route>` tag into a statement without effect
+    #[allow(clippy::unnecessary_operation)]
     fn view(&self, _ctx: &Context<Self>) -> Html {
         html! {
             <BrowserRouter>
@@ -27,6 +42,8 @@ impl Component for Main {
     }
 }
 
+// `html!` binds the unit properties of `App` and `Evaluation` to a variable
+#[allow(clippy::let_unit_value)]
 fn switch(routes: &Route) -> Html {
     match routes {
         Route::Home => html! {
@@ -38,16 +55,14 @@ fn switch(routes: &Route) -> Html {
     }
 }
 
-#[derive(Debug, thiserror::Error, PartialEq)]
+#[derive(Debug, thiserror::Error)]
 enum AppError {
     #[error("Error downloading file: {0}")]
     WebSys(String),
     #[error("Failed to read file: {0}")]
     FileReaderError(String),
-    #[error("Failed to convert file content to string")]
-    FileContentToStringError,
-    #[error("Error parsingfile: {0}")]
-    ParseError(String),
+    #[error("Error parsing file: {0}")]
+    ParseError(#[from] ParseError),
 }
 
 impl From<JsValue> for AppError {
@@ -61,9 +76,29 @@ type AppResult<T> = Result<T, AppError>;
 enum Msg {
     TextInput(String),
     XESImport(Option<File>),
+    CsvImport(Option<File>),
+    OcelImport(Option<File>),
     ExistentialThresholdInput(String),
     TemporalThresholdInput(String),
-    XESLoaded(AppResult<String>),
+    DirectThresholdInput(String),
+    ShowScores(bool),
+    ShowText(bool),
+    ClassifierSelected(usize),
+    CustomKeysInput(String),
+    ApplyCustomKeys,
+    LifecycleSelected(usize),
+    TimestampsSelected(usize),
+    TieBreakSelected(usize),
+    XESLoaded(AppResult<Vec<u8>>),
+    CsvLoaded(AppResult<Vec<u8>>),
+    OcelLoaded(AppResult<Vec<u8>>),
+    ObjectTypeSelected(usize),
+    CsvDelimiterSelected(usize),
+    CsvColumnSelected(CsvColumn, usize),
+    CsvTimestampFormatInput(String),
+    ApplyCsvColumns,
+    WorkerOutput(DiscoveryOutput),
+    CancelDiscovery,
     ConvertToXES,
     DownloadXES,
 }
@@ -74,10 +109,58 @@ struct AppState {
     processed: bool,
     existential_threshold: f64,
     temporal_threshold: f64,
+    direct_threshold: f64,
+    show_scores: bool,
+    /// Shows the discovered matrices as text instead of tables
+    show_text: bool,
+    classifier: Classifier,
+    custom_keys: String,
+    lifecycle: LifecyclePolicy,
+    timestamps: TimestampPolicy,
+    tie_break: Option<String>,
+}
+
+/// The classifiers of the log imported by the worker, which keeps the log to parse it again
+/// with another classifier.
+struct LoadedLog {
+    classifiers: AvailableClassifiers,
+}
+
+/// The columns of a CSV log chosen on import.
+#[derive(Clone, Copy)]
+enum CsvColumn {
+    Case,
+    Activity,
+    Timestamp,
+}
+
+/// An uploaded CSV log and the mapping of its columns, kept to import it again with other
+/// columns.
+struct CsvImport {
+    content: Vec<u8>,
+    header: Vec<String>,
+    options: CsvOptions,
+}
+
+/// The object types of an uploaded OCEL log and the one it is flattened on, `None` for all of
+/// them.
+struct OcelImport {
+    object_types: Vec<String>,
+    object_type: Option<usize>,
 }
 
 struct App {
     state: AppState,
+    log: Option<LoadedLog>,
+    csv: Option<CsvImport>,
+    ocel: Option<OcelImport>,
+    worker: Box<dyn Bridge<DiscoveryWorker>>,
+    /// Id of the running request to the worker, responses to other requests are ignored.
+    discovery: Option<usize>,
+    next_discovery: usize,
+    progress: Option<DiscoveryProgress>,
+    /// The matrices of the last discovery, shown as tables unless `show_text` is set
+    matrices: Option<Vec<DiscoveredMatrix>>,
     file_reader_closure: Option<Closure<dyn FnMut(web_sys::ProgressEvent)>>,
 }
 
@@ -85,14 +168,30 @@ impl Component for App {
     type Message = Msg;
     type Properties = ();
 
-    fn create(_ctx: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
         Self {
             state: AppState {
                 text: String::new(),
                 processed: false,
                 existential_threshold: 1.0,
                 temporal_threshold: 1.0,
+                direct_threshold: 1.0,
+                show_scores: false,
+                show_text: false,
+                classifier: Classifier::Name,
+                custom_keys: String::new(),
+                lifecycle: LifecyclePolicy::default(),
+                timestamps: TimestampPolicy::default(),
+                tie_break: None,
             },
+            log: None,
+            csv: None,
+            ocel: None,
+            worker: DiscoveryWorker::bridge(ctx.link().callback(Msg::WorkerOutput)),
+            discovery: None,
+            next_discovery: 0,
+            progress: None,
+            matrices: None,
             file_reader_closure: None,
         }
     }
@@ -101,12 +200,13 @@ impl Component for App {
         match msg {
             Msg::TextInput(text) => {
                 self.state.text = text;
+                self.matrices = None;
                 self.state.processed = false;
                 true
             }
             Msg::XESImport(file_option) => {
                 if let Some(file) = file_option {
-                    self.load_xes_file(ctx, file);
+                    self.read_file(ctx, file, Msg::XESLoaded);
                 }
                 false
             }
@@ -122,30 +222,208 @@ impl Component for App {
                 }
                 false
             }
+            Msg::DirectThresholdInput(value) => {
+                if let Ok(threshold) = value.parse::<f64>() {
+                    self.state.direct_threshold = threshold;
+                }
+                false
+            }
+            Msg::ShowScores(show_scores) => {
+                self.state.show_scores = show_scores;
+                if let Some(matrices) = &self.matrices {
+                    self.state.text = self.process_discovered_matrices(matrices);
+                }
+                true
+            }
+            Msg::ShowText(show_text) => {
+                self.state.show_text = show_text;
+                true
+            }
+            Msg::ClassifierSelected(index) => {
+                let classifier = self.log.as_ref().and_then(|log| {
+                    classifier_choices(&log.classifiers, &self.state.custom_keys)
+                        .into_iter()
+                        .nth(index)
+                });
+                if let Some(classifier) = classifier {
+                    // custom attributes are only applied once keys are entered
+                    let ready = !matches!(&classifier, Classifier::Keys(keys) if keys.is_empty());
+                    self.state.classifier = classifier;
+                    if ready {
+                        self.process_loaded_log();
+                    }
+                }
+                true
+            }
+            Msg::CustomKeysInput(keys) => {
+                self.state.custom_keys = keys;
+                false
+            }
+            Msg::ApplyCustomKeys => {
+                self.state.classifier = Classifier::Keys(split_keys(&self.state.custom_keys));
+                self.process_loaded_log();
+                true
+            }
+            Msg::LifecycleSelected(index) => {
+                if let Some(lifecycle) = LifecyclePolicy::ALL.get(index) {
+                    self.state.lifecycle = *lifecycle;
+                    self.process_loaded_log();
+                }
+                true
+            }
+            Msg::TimestampsSelected(index) => {
+                if let Some(timestamps) = TimestampPolicy::ALL.get(index) {
+                    self.state.timestamps = *timestamps;
+                    self.process_loaded_log();
+                }
+                true
+            }
+            Msg::TieBreakSelected(index) => {
+                // the first option orders ties in file order
+                self.state.tie_break = index.checked_sub(1).and_then(|index| {
+                    self.log
+                        .as_ref()
+                        .and_then(|log| log.classifiers.attribute_keys.get(index).cloned())
+                });
+                self.process_loaded_log();
+                true
+            }
+            Msg::CsvImport(file_option) => {
+                if let Some(file) = file_option {
+                    self.read_file(ctx, file, Msg::CsvLoaded);
+                }
+                false
+            }
+            Msg::OcelImport(file_option) => {
+                if let Some(file) = file_option {
+                    self.read_file(ctx, file, Msg::OcelLoaded);
+                }
+                false
+            }
             Msg::XESLoaded(result) => {
+                self.csv = None;
+                self.ocel = None;
                 match result {
-                    Ok(content) => match self.process_xes_content(&content) {
-                        Ok(processed_text) => {
-                            self.state.text = processed_text;
+                    Ok(content) => self.import_log(LogSource::Xes(content)),
+                    Err(e) => {
+                        self.log = None;
+                        self.state.text = format!("Error loading event log: {}", e);
+                        self.matrices = None;
+                    }
+                }
+                true
+            }
+            Msg::OcelLoaded(result) => {
+                self.csv = None;
+                match result {
+                    Ok(content) => {
+                        // the object types are known once the worker imported the log
+                        self.ocel = Some(OcelImport {
+                            object_types: Vec::new(),
+                            object_type: None,
+                        });
+                        self.import_log(LogSource::Ocel(content));
+                    }
+                    Err(e) => {
+                        self.ocel = None;
+                        self.log = None;
+                        self.state.text = format!("Error loading OCEL file: {}", e);
+                        self.matrices = None;
+                    }
+                }
+                true
+            }
+            Msg::ObjectTypeSelected(index) => {
+                if let Some(ocel) = &mut self.ocel {
+                    // the first option compares all object types
+                    ocel.object_type = index.checked_sub(1);
+                }
+                self.state.classifier = Classifier::Name;
+                self.state.tie_break = None;
+                self.process_loaded_log();
+                true
+            }
+            Msg::CsvLoaded(result) => {
+                self.ocel = None;
+                let csv = result.and_then(|content| {
+                    let options = CsvOptions::detect(&content, None)?;
+                    Ok(CsvImport {
+                        header: csv_header(&content, options.delimiter)?,
+                        content,
+                        options,
+                    })
+                });
+                match csv {
+                    Ok(csv) => {
+                        self.csv = Some(csv);
+                        self.import_csv();
+                    }
+                    Err(e) => {
+                        self.csv = None;
+                        self.log = None;
+                        self.state.text = format!("Error loading CSV file: {}", e);
+                        self.matrices = None;
+                    }
+                }
+                true
+            }
+            Msg::CsvDelimiterSelected(index) => {
+                if let (Some(csv), Some(delimiter)) = (&mut self.csv, DELIMITERS.get(index)) {
+                    // the columns of another delimiter are guessed again
+                    if let Ok(options) = CsvOptions::detect(&csv.content, Some(*delimiter)) {
+                        csv.header = csv_header(&csv.content, *delimiter).unwrap_or_default();
+                        csv.options = options;
+                    }
+                }
+                true
+            }
+            Msg::CsvColumnSelected(column, index) => {
+                if let Some(csv) = &mut self.csv {
+                    match column {
+                        CsvColumn::Case => {
+                            csv.options.case_column = csv.header[index].clone();
                         }
-                        Err(e) => {
-                            self.state.text = format!("Processing error: {}", e);
+                        CsvColumn::Activity => {
+                            csv.options.activity_column = csv.header[index].clone();
+                        }
+                        // the first option is no timestamp column
+                        CsvColumn::Timestamp => {
+                            csv.options.timestamp_column = index
+                                .checked_sub(1)
+                                .and_then(|index| csv.header.get(index).cloned());
                         }
-                    },
-                    Err(e) => {
-                        self.state.text = format!("Error loading XES file: {}", e);
                     }
                 }
                 true
             }
+            Msg::CsvTimestampFormatInput(format) => {
+                if let Some(csv) = &mut self.csv {
+                    csv.options.timestamp_format = Some(format).filter(|format| !format.is_empty());
+                }
+                false
+            }
+            Msg::ApplyCsvColumns => {
+                self.import_csv();
+                true
+            }
+            Msg::WorkerOutput(output) => self.handle_worker_output(output),
+            Msg::CancelDiscovery => {
+                self.worker.send(DiscoveryInput::Cancel);
+                self.discovery = None;
+                self.progress = None;
+                self.state.text = "Discovery cancelled".to_string();
+                true
+            }
             Msg::ConvertToXES => {
                 match self.generate_xes_output() {
                     Ok(xes_text) => {
                         self.state.text = xes_text;
+                        self.matrices = None;
                         self.state.processed = true;
                     }
                     Err(e) => {
                         self.state.text = format!("Conversion to XES failed: {}", e);
+                        self.matrices = None;
                     }
                 }
                 true
@@ -161,6 +439,8 @@ impl Component for App {
         }
     }
 
+    // `html!` expands the generic `Link<Route>` tag into a statement without effect
+    #[allow(clippy::unnecessary_operation)]
     fn view(&self, ctx: &Context<Self>) -> Html {
         let oninput = ctx.link().callback(|e: InputEvent| {
             let input: HtmlTextAreaElement = e.target_unchecked_into();
@@ -172,6 +452,16 @@ impl Component for App {
             Msg::XESImport(input.files().and_then(|files| files.get(0)))
         });
 
+        let oncsvimport = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::CsvImport(input.files().and_then(|files| files.get(0)))
+        });
+
+        let onocelimport = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::OcelImport(input.files().and_then(|files| files.get(0)))
+        });
+
         let onexistential_threshold_input = ctx.link().callback(|e: InputEvent| {
             let input: HtmlInputElement = e.target_unchecked_into();
             Msg::ExistentialThresholdInput(input.value())
@@ -182,17 +472,54 @@ impl Component for App {
             Msg::TemporalThresholdInput(input.value())
         });
 
+        let ondirect_threshold_input = ctx.link().callback(|e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::DirectThresholdInput(input.value())
+        });
+
+        let onshow_scores = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::ShowScores(input.checked())
+        });
+
+        let onshow_text = ctx.link().callback(|e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::ShowText(input.checked())
+        });
+
+        let onclassifier = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            Msg::ClassifierSelected(select.selected_index() as usize)
+        });
+
+        let oncustom_keys_input = ctx.link().callback(|e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::CustomKeysInput(input.value())
+        });
+
+        let onapply_custom_keys = ctx.link().callback(|_| Msg::ApplyCustomKeys);
+
+        let onlifecycle = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            Msg::LifecycleSelected(select.selected_index() as usize)
+        });
+
+        let ontimestamps = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            Msg::TimestampsSelected(select.selected_index() as usize)
+        });
+
+        let ontie_break = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            Msg::TieBreakSelected(select.selected_index() as usize)
+        });
+
         let onprocess = ctx.link().callback(|_| Msg::ConvertToXES);
         let ondownload = ctx.link().callback(|_| Msg::DownloadXES);
 
         html! {
             <div style="height: 90vh; display: flex; flex-direction: column;">
-                <textarea
-                    value={self.state.text.clone()}
-                    oninput={oninput}
-                    placeholder="Enter your text here"
-                    style="flex-grow: 1; width: 99%; background-color: #393939; color: white; padding: 10px; font-size: 16px; resize: none;"
-                />
+                {self.output_view(oninput)}
                 <div style="display: flex; flex-wrap: wrap; padding: 10px; align-items: center;">
                     <div style="display: flex; align-items: center; margin-right: 20px;">
                         <label for="temporal-threshold" style="margin-right: 10px; font-size: 14px;">
@@ -209,7 +536,7 @@ impl Component for App {
                             style="width: 70px; padding: 5px; font-size: 14px; border-radius: 4px; border: 1px solid #ccc;"
                         />
                     </div>
-                    <div style="display: flex; align-items: center; margin-right: auto;">
+                    <div style="display: flex; align-items: center; margin-right: 20px;">
                         <label for="existential-threshold" style="margin-right: 10px; font-size: 14px;">
                             {"Existential Threshold:"}
                         </label>
@@ -224,11 +551,58 @@ impl Component for App {
                             style="width: 70px; padding: 5px; font-size: 14px; border-radius: 4px; border: 1px solid #ccc;"
                         />
                     </div>
+                    <div style="display: flex; align-items: center; margin-right: 20px;">
+                        <label for="direct-threshold" style="margin-right: 10px; font-size: 14px;">
+                            {"Direct Threshold:"}
+                        </label>
+                        <input
+                            id="direct-threshold"
+                            type="number"
+                            min="0"
+                            max="1.0"
+                            step="0.1"
+                            value={self.state.direct_threshold.to_string()}
+                            oninput={ondirect_threshold_input}
+                            style="width: 70px; padding: 5px; font-size: 14px; border-radius: 4px; border: 1px solid #ccc;"
+                        />
+                    </div>
+                    <div style="display: flex; align-items: center; margin-right: 20px;">
+                        <input
+                            id="show-scores"
+                            type="checkbox"
+                            checked={self.state.show_scores}
+                            onchange={onshow_scores}
+                            style="margin-right: 5px;"
+                        />
+                        <label for="show-scores" style="font-size: 14px;" title="Show the ratio of occurrences or traces that satisfy each dependency">
+                            {"Show Confidence"}
+                        </label>
+                    </div>
+                    <div style="display: flex; align-items: center; margin-right: auto;">
+                        <input
+                            id="show-text"
+                            type="checkbox"
+                            checked={self.state.show_text}
+                            onchange={onshow_text}
+                            style="margin-right: 5px;"
+                        />
+                        <label for="show-text" style="font-size: 14px;" title="Show the discovered matrices as text, e.g. to copy them">
+                            {"Show as Text"}
+                        </label>
+                    </div>
                     <div style="display: flex; margin-left: auto;">
-                        <input type="file" id="xes-file" accept=".xes" onchange={onxesimport} style="display: none;" />
+                        <input type="file" id="xes-file" accept=".xes,.gz" onchange={onxesimport} style="display: none;" />
                         <label for="xes-file" style="padding: 10px 20px; font-size: 16px; margin-right: 10px; background-color: #4CAF50; color: white; cursor: pointer; border-radius: 5px;">
                             {"Import XES"}
                         </label>
+                        <input type="file" id="csv-file" accept=".csv,.txt" onchange={oncsvimport} style="display: none;" />
+                        <label for="csv-file" style="padding: 10px 20px; font-size: 16px; margin-right: 10px; background-color: #4CAF50; color: white; cursor: pointer; border-radius: 5px;">
+                            {"Import CSV"}
+                        </label>
+                        <input type="file" id="ocel-file" accept=".jsonocel,.xmlocel,.json,.xml" onchange={onocelimport} style="display: none;" />
+                        <label for="ocel-file" style="padding: 10px 20px; font-size: 16px; margin-right: 10px; background-color: #4CAF50; color: white; cursor: pointer; border-radius: 5px;">
+                            {"Import OCEL"}
+                        </label>
                         <button onclick={onprocess} disabled={self.state.processed} style="padding: 10px 20px; font-size: 16px; margin-right: 10px;">
                             {"Convert To XES"}
                         </button>
@@ -237,6 +611,10 @@ impl Component for App {
                         </button>
                     </div>
                 </div>
+                {self.csv_view(ctx)}
+                {self.ocel_view(ctx)}
+                {self.progress_view(ctx)}
+                {self.classifier_view(onclassifier, oncustom_keys_input, onapply_custom_keys, onlifecycle, ontimestamps, ontie_break)}
                 <div style="color: white; font-size: 16px; margin-top: 10px; margin-right: 10px; text-align: right;">
                     <Link<Route> to={Route::Evaluation}>{ "Evaluation" }</Link<Route>>
                 </div>
@@ -246,7 +624,13 @@ impl Component for App {
 }
 
 impl App {
-    fn load_xes_file(&mut self, ctx: &Context<Self>, file: File) {
+    /// Reads the content of `file` and sends it to the component as the message `loaded`.
+    fn read_file(
+        &mut self,
+        ctx: &Context<Self>,
+        file: File,
+        loaded: fn(AppResult<Vec<u8>>) -> Msg,
+    ) {
         let link = ctx.link().clone();
         let reader = FileReader::new().unwrap_throw();
         let reader_clone = reader.clone();
@@ -255,108 +639,415 @@ impl App {
             let result = reader_clone
                 .result()
                 .map_err(|e| AppError::FileReaderError(format!("{:?}", e)))
-                .and_then(|result| result.as_string().ok_or(AppError::FileContentToStringError));
-            link.send_message(Msg::XESLoaded(result));
+                .map(|result| js_sys::Uint8Array::new(&result).to_vec());
+            link.send_message(loaded(result));
         });
 
         reader.set_onload(Some(onload.as_ref().unchecked_ref()));
         self.file_reader_closure = Some(onload);
 
-        if let Err(e) = reader.read_as_text(&file) {
+        if let Err(e) = reader.read_as_array_buffer(&file) {
             let error_link = ctx.link().clone(); // Clone link here for the error case
-            error_link.send_message(Msg::XESLoaded(Err(AppError::FileReaderError(format!(
-                "{:?}",
-                e
-            )))));
+            error_link.send_message(loaded(Err(AppError::FileReaderError(format!("{:?}", e)))));
         }
     }
 
-    fn process_xes_content(&self, content: &str) -> AppResult<String> {
-        let traces = parse_into_traces(None, Some(content))
-            .map_err(|e| AppError::ParseError(format!("{:?}", e)))?;
-
-        let (
-            adj_matrix,
-            full_independences,
-            pure_existences,
-            _eventual_equivalences,
-            _direct_equivalences,
-            number_of_activities,
-            _relationship_counts,
-        ) = generate_adj_matrix_from_traces(
-            traces.clone(),
-            self.state.existential_threshold,
-            self.state.temporal_threshold,
-        );
-        let relations = number_of_activities * number_of_activities;
-        let _independences_per_relations = full_independences as f64 / relations as f64;
-        let _temporal_independences_per_relations = pure_existences as f64 / relations as f64;
-        let traces_as_str: Vec<Vec<&str>> = traces
+    /// Selection of the classifier, lifecycle and timestamp policy and tie-break of the imported
+    /// log, hidden until a log is imported.
+    fn classifier_view(
+        &self,
+        onclassifier: Callback<Event>,
+        oncustom_keys_input: Callback<InputEvent>,
+        onapply_custom_keys: Callback<MouseEvent>,
+        onlifecycle: Callback<Event>,
+        ontimestamps: Callback<Event>,
+        ontie_break: Callback<Event>,
+    ) -> Html {
+        let Some(log) = &self.log else {
+            return html! {};
+        };
+        let is_custom = matches!(self.state.classifier, Classifier::Keys(_));
+        let keys_of = |name: &str| {
+            log.classifiers
+                .declared
+                .iter()
+                .find(|classifier| classifier.name == name)
+                .map(|classifier| classifier.keys.join(" + "))
+                .unwrap_or_default()
+        };
+
+        html! {
+            <div style="display: flex; flex-wrap: wrap; padding: 0 10px; align-items: center;">
+                <label for="classifier" style="margin-right: 10px; font-size: 14px;">
+                    {"Classifier:"}
+                </label>
+                <select id="classifier" onchange={onclassifier} style="padding: 5px; font-size: 14px; margin-right: 10px;">
+                    {for classifier_choices(&log.classifiers, &self.state.custom_keys).into_iter().map(|classifier| {
+                        let label = match &classifier {
+                            Classifier::Name => "concept:name".to_string(),
+                            Classifier::Declared(name) => format!("{} ({})", name, keys_of(name)),
+                            Classifier::Keys(_) => "Custom attributes".to_string(),
+                        };
+                        let selected = match (&classifier, &self.state.classifier) {
+                            (Classifier::Keys(_), Classifier::Keys(_)) => true,
+                            (classifier, current) => classifier == current,
+                        };
+                        html! { <option selected={selected}>{label}</option> }
+                    })}
+                </select>
+                if is_custom {
+                    <input
+                        type="text"
+                        value={self.state.custom_keys.clone()}
+                        oninput={oncustom_keys_input}
+                        placeholder={format!("Comma-separated keys, e.g. {}", log.classifiers.attribute_keys.join(", "))}
+                        style="width: 400px; padding: 5px; font-size: 14px; margin-right: 10px;"
+                    />
+                    <button onclick={onapply_custom_keys} style="padding: 5px 10px; font-size: 14px; margin-right: 10px;">
+                        {"Apply"}
+                    </button>
+                }
+                <label for="lifecycle" style="margin: 0 10px; font-size: 14px;">
+                    {"Lifecycle:"}
+                </label>
+                <select id="lifecycle" onchange={onlifecycle} style="padding: 5px; font-size: 14px;">
+                    {for LifecyclePolicy::ALL.iter().map(|lifecycle| html! {
+                        <option selected={*lifecycle == self.state.lifecycle}>{lifecycle.to_string()}</option>
+                    })}
+                </select>
+                <label for="timestamps" style="margin: 0 10px; font-size: 14px;">
+                    {"Order:"}
+                </label>
+                <select id="timestamps" onchange={ontimestamps} style="padding: 5px; font-size: 14px;">
+                    {for TimestampPolicy::ALL.iter().map(|timestamps| html! {
+                        <option selected={*timestamps == self.state.timestamps}>{timestamps.to_string()}</option>
+                    })}
+                </select>
+                <label for="tie-break" style="margin: 0 10px; font-size: 14px;" title="Order of events with the same timestamp">
+                    {"Ties:"}
+                </label>
+                <select id="tie-break" onchange={ontie_break} style="padding: 5px; font-size: 14px;">
+                    <option selected={self.state.tie_break.is_none()}>{"file order"}</option>
+                    {for log.classifiers.attribute_keys.iter().map(|key| html! {
+                        <option selected={self.state.tie_break.as_ref() == Some(key)}>{key}</option>
+                    })}
+                </select>
+            </div>
+        }
+    }
+
+    /// Sends an uploaded log to the worker, which replaces the imported log by it and
+    /// discovers its matrix with the default classifier.
+    fn import_log(&mut self, source: LogSource) {
+        // the classifiers are known once the worker imported the log
+        self.log = None;
+        self.state.classifier = Classifier::Name;
+        self.state.tie_break = None;
+        let id = self.start_discovery();
+        let options = self.discovery_options();
+        self.worker.send(DiscoveryInput::Import {
+            id,
+            source,
+            options,
+        });
+    }
+
+    /// Imports the uploaded CSV log with the chosen columns.
+    fn import_csv(&mut self) {
+        let Some(csv) = &self.csv else {
+            return;
+        };
+        let source = LogSource::Csv(csv.content.clone(), csv.options.clone());
+        self.import_log(source);
+    }
+
+    /// Choice of the object type to flatten an uploaded OCEL log on, hidden unless an OCEL log
+    /// is uploaded.
+    fn ocel_view(&self, ctx: &Context<Self>) -> Html {
+        let Some(ocel) = &self.ocel else {
+            return html! {};
+        };
+        let onobject_type = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            Msg::ObjectTypeSelected(select.selected_index() as usize)
+        });
+
+        html! {
+            <div style="display: flex; flex-wrap: wrap; padding: 0 10px 10px; align-items: center; font-size: 14px;">
+                <label for="object-type" style="margin-right: 10px;">{"Object type:"}</label>
+                <select id="object-type" onchange={onobject_type} style="padding: 5px;">
+                    <option selected={ocel.object_type.is_none()}>{"all object types"}</option>
+                    {for ocel.object_types.iter().enumerate().map(|(index, object_type)| html! {
+                        <option selected={ocel.object_type == Some(index)}>{object_type}</option>
+                    })}
+                </select>
+            </div>
+        }
+    }
+
+    /// Choice of the delimiter and columns of an uploaded CSV log, hidden unless a CSV log is
+    /// uploaded.
+    fn csv_view(&self, ctx: &Context<Self>) -> Html {
+        let Some(csv) = &self.csv else {
+            return html! {};
+        };
+        let onselect = |column: CsvColumn| {
+            ctx.link().callback(move |e: Event| {
+                let select: HtmlSelectElement = e.target_unchecked_into();
+                Msg::CsvColumnSelected(column, select.selected_index() as usize)
+            })
+        };
+        let ondelimiter = ctx.link().callback(|e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            Msg::CsvDelimiterSelected(select.selected_index() as usize)
+        });
+        let ontimestamp_format = ctx.link().callback(|e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            Msg::CsvTimestampFormatInput(input.value())
+        });
+        let onapply = ctx.link().callback(|_| Msg::ApplyCsvColumns);
+        let column_options = |selected: Option<&String>| {
+            csv.header
+                .iter()
+                .map(|column| html! { <option selected={selected == Some(column)}>{column}</option> })
+                .collect::<Html>()
+        };
+
+        html! {
+            <div style="display: flex; flex-wrap: wrap; padding: 0 10px 10px; align-items: center; font-size: 14px;">
+                <label for="csv-delimiter" style="margin-right: 10px;">{"Delimiter:"}</label>
+                <select id="csv-delimiter" onchange={ondelimiter} style="padding: 5px; margin-right: 10px;">
+                    {for DELIMITERS.iter().map(|delimiter| {
+                        let label = match delimiter {
+                            '\t' => "Tab".to_string(),
+                            delimiter => delimiter.to_string(),
+                        };
+                        html! { <option selected={*delimiter == csv.options.delimiter}>{label}</option> }
+                    })}
+                </select>
+                <label for="csv-case" style="margin-right: 10px;">{"Case:"}</label>
+                <select id="csv-case" onchange={onselect(CsvColumn::Case)} style="padding: 5px; margin-right: 10px;">
+                    {column_options(Some(&csv.options.case_column))}
+                </select>
+                <label for="csv-activity" style="margin-right: 10px;">{"Activity:"}</label>
+                <select id="csv-activity" onchange={onselect(CsvColumn::Activity)} style="padding: 5px; margin-right: 10px;">
+                    {column_options(Some(&csv.options.activity_column))}
+                </select>
+                <label for="csv-timestamp" style="margin-right: 10px;">{"Timestamp:"}</label>
+                <select id="csv-timestamp" onchange={onselect(CsvColumn::Timestamp)} style="padding: 5px; margin-right: 10px;">
+                    <option selected={csv.options.timestamp_column.is_none()}>{"none"}</option>
+                    {column_options(csv.options.timestamp_column.as_ref())}
+                </select>
+                <input
+                    type="text"
+                    value={csv.options.timestamp_format.clone().unwrap_or_default()}
+                    oninput={ontimestamp_format}
+                    placeholder="Timestamp format, e.g. %d.%m.%Y %H:%M"
+                    style="width: 250px; padding: 5px; font-size: 14px; margin-right: 10px;"
+                />
+                <button onclick={onapply} style="padding: 5px 10px; font-size: 14px;">
+                    {"Import Columns"}
+                </button>
+            </div>
+        }
+    }
+
+    /// Discovers the matrix of the imported log again with the current thresholds and
+    /// classifier.
+    fn process_loaded_log(&mut self) {
+        if self.log.is_none() {
+            return;
+        }
+        let id = self.start_discovery();
+        let options = self.discovery_options();
+        self.worker.send(DiscoveryInput::Discover { id, options });
+    }
+
+    /// Assigns the id of a new request to the worker, whose responses replace the ones of the
+    /// running request.
+    fn start_discovery(&mut self) -> usize {
+        let id = self.next_discovery;
+        self.next_discovery += 1;
+        self.discovery = Some(id);
+        self.progress = None;
+        self.state.text = "Discovering the matrix...".to_string();
+        self.matrices = None;
+        id
+    }
+
+    fn discovery_options(&self) -> DiscoveryOptions {
+        DiscoveryOptions {
+            parse: ParseOptions {
+                classifier: self.state.classifier.clone(),
+                lifecycle: self.state.lifecycle,
+                timestamps: self.state.timestamps,
+                tie_break: self.state.tie_break.clone(),
+            },
+            object_type: self.ocel.as_ref().and_then(|ocel| {
+                ocel.object_type
+                    .and_then(|index| ocel.object_types.get(index).cloned())
+            }),
+            existential_threshold: self.state.existential_threshold,
+            temporal_threshold: self.state.temporal_threshold,
+            direct_threshold: self.state.direct_threshold,
+        }
+    }
+
+    fn handle_worker_output(&mut self, output: DiscoveryOutput) -> bool {
+        match output {
+            DiscoveryOutput::Loaded {
+                id,
+                classifiers,
+                object_types,
+            } if self.discovery == Some(id) => {
+                self.log = Some(LoadedLog { classifiers });
+                if let Some(ocel) = &mut self.ocel {
+                    ocel.object_types = object_types;
+                }
+            }
+            DiscoveryOutput::Progress { id, progress } if self.discovery == Some(id) => {
+                self.progress = Some(progress);
+            }
+            DiscoveryOutput::Discovered { id, matrices } if self.discovery == Some(id) => {
+                self.discovery = None;
+                self.progress = None;
+                self.state.text = self.process_discovered_matrices(&matrices);
+                self.matrices = Some(matrices);
+            }
+            DiscoveryOutput::Failed { id, error } if self.discovery == Some(id) => {
+                self.discovery = None;
+                self.progress = None;
+                self.log = None;
+                self.ocel = None;
+                self.state.text = format!("Error loading event log: {}", error);
+                self.matrices = None;
+            }
+            // responses to cancelled or replaced requests
+            _ => return false,
+        }
+        true
+    }
+
+    /// The discovered matrices as tables, or the text area with the input, the matrices as text
+    /// or the status of the last action.
+    // `html!` binds the unit properties of `MatrixLegend` to a variable
+    #[allow(clippy::let_unit_value)]
+    fn output_view(&self, oninput: Callback<InputEvent>) -> Html {
+        let matrices = match &self.matrices {
+            Some(matrices) if !self.state.show_text => matrices,
+            _ => {
+                return html! {
+                    <textarea
+                        value={self.state.text.clone()}
+                        oninput={oninput}
+                        placeholder="Enter your text here"
+                        style="flex-grow: 1; width: 99%; background-color: #393939; color: white; padding: 10px; font-size: 16px; resize: none;"
+                    />
+                }
+            }
+        };
+
+        html! {
+            <div style="flex-grow: 1; overflow: auto; padding: 10px; background-color: white;">
+                <MatrixLegend />
+                {for matrices.iter().map(|discovered| html! {
+                    <div style="margin-bottom: 20px;">
+                        if let Some(object_type) = &discovered.object_type {
+                            <h3 style="margin: 10px 0 5px;">{format!("Object type {}", object_type)}</h3>
+                        }
+                        {match &discovered.result {
+                            Ok((adj_matrix, report)) => html! {
+                                <>
+                                    <MatrixTable matrix={adj_matrix.clone()} show_scores={self.state.show_scores} />
+                                    <pre style="font-size: 13px;">{discovery_summary(adj_matrix, report)}</pre>
+                                </>
+                            },
+                            Err(e) => html! {
+                                <pre style="color: #dc3545;">{format!("Processing error: {}", e)}</pre>
+                            },
+                        }}
+                    </div>
+                })}
+            </div>
+        }
+    }
+
+    /// Progress of the running discovery with a button to cancel it, hidden unless the worker
+    /// is running.
+    fn progress_view(&self, ctx: &Context<Self>) -> Html {
+        if self.discovery.is_none() {
+            return html! {};
+        }
+        let oncancel = ctx.link().callback(|_| Msg::CancelDiscovery);
+        let (label, done, total) = match self.progress {
+            None => ("Starting the discovery".to_string(), None, None),
+            Some(DiscoveryProgress::Importing) => ("Importing the log".to_string(), None, None),
+            Some(DiscoveryProgress::Parsing { traces, total }) => (
+                format!(
+                    "Parsing: {} of {} traces ({}%)",
+                    traces,
+                    total,
+                    traces * 100 / total.max(1)
+                ),
+                Some(traces),
+                Some(total),
+            ),
+            Some(DiscoveryProgress::Discovering { pairs, total }) => (
+                format!("Discovering: {} of {} pairs of activities", pairs, total),
+                Some(pairs),
+                Some(total),
+            ),
+        };
+
+        // a progress without value is shown as indeterminate
+        html! {
+            <div style="display: flex; flex-wrap: wrap; padding: 0 10px 10px; align-items: center; font-size: 14px;">
+                <progress
+                    value={done.map(|done| done.to_string())}
+                    max={total.map(|total| total.to_string())}
+                    style="width: 300px; margin-right: 10px;"
+                />
+                <span style="margin-right: 10px;">{label}</span>
+                <button onclick={oncancel} style="padding: 5px 10px; font-size: 14px;">
+                    {"Cancel"}
+                </button>
+            </div>
+        }
+    }
+
+    /// The text shown for the discovered matrices.
+    fn process_discovered_matrices(&self, matrices: &[DiscoveredMatrix]) -> String {
+        // the matrices of the object types of an OCEL log are shown one after another
+        matrices
             .iter()
-            .map(|trace| trace.iter().map(|s| s.as_str()).collect())
-            .collect();
-        let variants = variants_of_traces(traces_as_str);
-        let max_variant_frequency = *variants.values().max().unwrap() as f64 / traces.len() as f64;
-        let _variants_per_traces = variants.len() as f64 / traces.len() as f64;
-        let _freq_over_variants = max_variant_frequency / variants.len() as f64;
-
-        let plain_log: Vec<Vec<matrix_discovery::event::Event>> = traces
-            .clone()
-            .into_iter()
-            .enumerate()
-            .map(|(case_idx, trace)| {
-                trace
-                    .into_iter()
-                    .enumerate()
-                    .map(|(event_idx, activity)| matrix_discovery::event::Event {
-                        case: format!("case_{}", case_idx),
-                        activity: activity.chars().next().unwrap(),
-                        predecessor: if event_idx > 0 {
-                            Some(format!("case_{}", case_idx))
-                        } else {
-                            None
-                        },
-                    })
-                    .collect()
+            .map(|discovered| {
+                let processed_text = match &discovered.result {
+                    Ok((adj_matrix, report)) => self.process_discovered_matrix(adj_matrix, report),
+                    Err(e) => format!("Processing error: {}", e),
+                };
+                match &discovered.object_type {
+                    Some(object_type) => {
+                        format!("Object type {}\n\n{}", object_type, processed_text)
+                    }
+                    None => processed_text,
+                }
             })
-            .collect();
-
-        let epa = matrix_discovery::epa::ExtendedPrefixAutomaton::build(plain_log);
-        let _variant_entropy = epa.variant_entropy();
-        let _normalized_variant_entropy = epa.normalized_variant_entropy();
-
-        Ok(format!(
-            "{}\n\n\
-            #relations: {}",
-            //#independence / #relations:        {:<10.4}\n\
-            //#temporal independence / #relations: {:<10.4}\n\
-            //max. frequency of variants / total #traces: {:<10.4}\n\
-            //#variants / total #traces:          {:<10.4}\n\
-            //#(Eventual, <=>):                    {:<10}\n\
-            //#(Direct, <=>):                      {:<10}\n\
-            //#variants:                          {:<10}\n\
-            //max. frequency of variants / #variants:     {:<10.4}\n\
-            //Variant Entropy:                     {:<10.4}\n\
-            //Normalized Variant Entropy:          {:<10.4}\n\n\
-            //Relationship Type Frequencies:\n{}",
-            adj_matrix,
-            relations,
-            //independences_per_relations,
-            //temporal_independences_per_relations,
-            //max_variant_frequency,
-            //variants_per_traces,
-            //eventual_equivalences,
-            //direct_equivalences,
-            //variants.len() as f64,
-            //freq_over_variants,
-            //variant_entropy,
-            //normalized_variant_entropy,
-            //relationship_counts
-            //    .iter()
-            //    .map(|(k, v)| format!("{}: {}", k, v))
-            //    .collect::<Vec<String>>()
-            //    .join("\n")
-        ))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// The text shown for a discovered matrix and the report of parsing its log.
+    fn process_discovered_matrix(
+        &self,
+        adj_matrix: &AdjacencyMatrix,
+        report: &ParseReport,
+    ) -> String {
+        let matrix = if self.state.show_scores {
+            format!("{:#}", adj_matrix)
+        } else {
+            adj_matrix.to_string()
+        };
+        format!("{}\n\n{}", matrix, discovery_summary(adj_matrix, report))
     }
 
     fn generate_xes_output(&self) -> AppResult<String> {
@@ -390,6 +1081,70 @@ impl App {
     }
 }
 
+/// The report of parsing the log of a discovered matrix and the number of its relations.
+fn discovery_summary(adj_matrix: &AdjacencyMatrix, report: &ParseReport) -> String {
+    let metrics = adj_matrix.metrics();
+    let relations = adj_matrix.len() * adj_matrix.len();
+    let _independences_per_relations = metrics.full_independences as f64 / relations as f64;
+    let _temporal_independences_per_relations = metrics.pure_existences as f64 / relations as f64;
+
+    format!(
+        "{}\n\
+        #relations: {}",
+        //#independence / #relations:        {:<10.4}\n\
+        //#temporal independence / #relations: {:<10.4}\n\
+        //max. frequency of variants / total #traces: {:<10.4}\n\
+        //#variants / total #traces:          {:<10.4}\n\
+        //#(Eventual, <=>):                    {:<10}\n\
+        //#(Direct, <=>):                      {:<10}\n\
+        //#variants:                          {:<10}\n\
+        //max. frequency of variants / #variants:     {:<10.4}\n\
+        //Variant Entropy:                     {:<10.4}\n\
+        //Normalized Variant Entropy:          {:<10.4}\n\n\
+        //Relationship Type Frequencies:\n{}",
+        report,
+        relations,
+        //independences_per_relations,
+        //temporal_independences_per_relations,
+        //max_variant_frequency,
+        //variants_per_traces,
+        //metrics.eventual_equivalences,
+        //metrics.direct_equivalences,
+        //variants.len() as f64,
+        //freq_over_variants,
+        //variant_entropy,
+        //normalized_variant_entropy,
+        //metrics
+        //    .relationship_counts
+        //    .iter()
+        //    .map(|(k, v)| format!("{}: {}", k, v))
+        //    .collect::<Vec<String>>()
+        //    .join("\n")
+    )
+}
+
+/// The classifiers offered for a log: `concept:name`, the ones declared in the log and the
+/// custom attribute keys.
+fn classifier_choices(classifiers: &AvailableClassifiers, custom_keys: &str) -> Vec<Classifier> {
+    std::iter::once(Classifier::Name)
+        .chain(
+            classifiers
+                .declared
+                .iter()
+                .map(|classifier| Classifier::Declared(classifier.name.clone())),
+        )
+        .chain(std::iter::once(Classifier::Keys(split_keys(custom_keys))))
+        .collect()
+}
+
+fn split_keys(keys: &str) -> Vec<String> {
+    keys.split(',')
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .map(String::from)
+        .collect()
+}
+
 fn main() {
     yew::start_app::<Main>();
 }
@@ -0,0 +1,6 @@
+use matrix_discovery::worker::DiscoveryWorker;
+use yew_agent::Threaded;
+
+fn main() {
+    DiscoveryWorker::register();
+}
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
 use crate::dependency_types::existential::ExistentialDependency;
@@ -5,7 +6,7 @@ use crate::dependency_types::temporal::TemporalDependency;
 
 use super::{existential, temporal};
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Dependency {
     pub from: String,
     pub to: String,
@@ -38,6 +39,9 @@ impl std::fmt::Display for Dependency {
     /// - If only `temporal_dependency` is present, it writes it followed by a comma and a dash.
     /// - If only `existential_dependency` is present, it writes a dash followed by the dependency.
     /// - If neither dependency is present, it writes "None".
+    ///
+    /// The alternate form (`{:#}`) follows the dependencies with their confidences, e.g.
+    /// `≺d,=> (0.95,1.00)` or `-,⇔ (-,0.80)`.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let temporal_dep = self.temporal_dependency.as_ref().map(|dep| dep.to_string());
         let existential_dep = self
@@ -46,44 +50,241 @@ impl std::fmt::Display for Dependency {
             .map(|dep| dep.to_string());
 
         match (temporal_dep, existential_dep) {
-            (Some(t), Some(e)) => write!(f, "{},{}", t, e),
-            (Some(t), None) => write!(f, "{},-", t),
-            (None, Some(e)) => write!(f, "-,{}", e),
-            (None, None) => write!(f, "None"),
+            (Some(t), Some(e)) => write!(f, "{},{}", t, e)?,
+            (Some(t), None) => write!(f, "{},-", t)?,
+            (None, Some(e)) => write!(f, "-,{}", e)?,
+            (None, None) => return write!(f, "None"),
+        }
+
+        if f.alternate() {
+            let confidence = |confidence: Option<f64>| {
+                confidence.map_or("-".to_string(), |confidence| format!("{:.2}", confidence))
+            };
+            write!(
+                f,
+                " ({},{})",
+                confidence(self.temporal_dependency.as_ref().map(|d| d.confidence)),
+                confidence(self.existential_dependency.as_ref().map(|d| d.confidence))
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Dependency {
+    /// Parses a cell of a rendered adjacency matrix, e.g. `≺d,=>`, `-,⇔` or `None`,
+    /// as the dependency between `from` and `to`.
+    ///
+    /// Symmetric existential dependencies are assumed to hold in both directions.
+    pub fn from_cell(from: &str, to: &str, cell: &str) -> Result<Self, ParseDependencyError> {
+        let cell = cell.trim();
+        if cell == "None" {
+            return Ok(Dependency::new(
+                from.to_string(),
+                to.to_string(),
+                None,
+                None,
+            ));
+        }
+
+        let (temporal_token, existential_token) =
+            cell.split_once(',').ok_or(ParseDependencyError {
+                column: cell.chars().count() + 1,
+                token: String::new(),
+                kind: ParseDependencyErrorKind::Missing("existential dependency"),
+            })?;
+        let error = |column, token: &str, kind| ParseDependencyError {
+            column,
+            token: token.to_string(),
+            kind,
+        };
+
+        let temporal_dependency = match temporal_token {
+            "≺d" => Some((
+                temporal::DependencyType::Direct,
+                temporal::Direction::Forward,
+            )),
+            "≻d" => Some((
+                temporal::DependencyType::Direct,
+                temporal::Direction::Backward,
+            )),
+            "≺" => Some((
+                temporal::DependencyType::Eventual,
+                temporal::Direction::Forward,
+            )),
+            "≻" => Some((
+                temporal::DependencyType::Eventual,
+                temporal::Direction::Backward,
+            )),
+            "-" => None,
+            _ => {
+                return Err(error(
+                    1,
+                    temporal_token,
+                    ParseDependencyErrorKind::InvalidTemporalType,
+                ))
+            }
+        };
+
+        let existential_dependency = match existential_token {
+            "=>" => Some((
+                existential::DependencyType::Implication,
+                existential::Direction::Forward,
+            )),
+            "<=" => Some((
+                existential::DependencyType::Implication,
+                existential::Direction::Backward,
+            )),
+            "⇔" => Some((
+                existential::DependencyType::Equivalence,
+                existential::Direction::Both,
+            )),
+            "⇎" => Some((
+                existential::DependencyType::NegatedEquivalence,
+                existential::Direction::Both,
+            )),
+            "⊼" => Some((
+                existential::DependencyType::Nand,
+                existential::Direction::Both,
+            )),
+            "∨" => Some((
+                existential::DependencyType::Or,
+                existential::Direction::Both,
+            )),
+            "∀" => Some((
+                existential::DependencyType::Always,
+                existential::Direction::Both,
+            )),
+            "∃" => Some((
+                existential::DependencyType::Sometimes,
+                existential::Direction::Both,
+            )),
+            "-" => None,
+            _ => {
+                return Err(error(
+                    temporal_token.chars().count() + 2,
+                    existential_token,
+                    ParseDependencyErrorKind::InvalidExistentialType,
+                ))
+            }
+        };
+
+        Ok(Dependency::new(
+            from.to_string(),
+            to.to_string(),
+            temporal_dependency.map(|(dependency_type, direction)| {
+                TemporalDependency::new(from, to, dependency_type, direction)
+            }),
+            existential_dependency.map(|(dependency_type, direction)| {
+                ExistentialDependency::new(from, to, dependency_type, direction)
+            }),
+        ))
+    }
+}
+
+/// Error returned when a dependency line like `a,b:d,f i,b` cannot be parsed.
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+#[error("column {column}: {kind}{}", format_token(token))]
+pub struct ParseDependencyError {
+    /// 1-based character column of the offending token, or of the end of the line if a token is missing
+    pub column: usize,
+    /// The offending token, empty if a token is missing
+    pub token: String,
+    pub kind: ParseDependencyErrorKind,
+}
+
+/// The offending token of an error message in backticks, preceded by a space, or nothing if
+/// the token is missing.
+pub(crate) fn format_token(token: &str) -> String {
+    if token.is_empty() {
+        String::new()
+    } else {
+        format!(" `{}`", token)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ParseDependencyErrorKind {
+    #[error("missing {0}")]
+    Missing(&'static str),
+    #[error("invalid temporal dependency type")]
+    InvalidTemporalType,
+    #[error("invalid temporal direction")]
+    InvalidTemporalDirection,
+    #[error("invalid existential dependency type")]
+    InvalidExistentialType,
+    #[error("invalid existential direction")]
+    InvalidExistentialDirection,
+    #[error("unexpected token")]
+    UnexpectedToken,
+}
+
+/// Splits a dependency line at commas, colons and whitespace and returns every token
+/// together with its 1-based character column.
+fn tokenize(s: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut token_start: Option<(usize, usize)> = None;
+
+    for (column, (byte, c)) in s.char_indices().enumerate() {
+        if c == ',' || c == ':' || c.is_whitespace() {
+            if let Some((start, start_column)) = token_start.take() {
+                tokens.push((start_column + 1, &s[start..byte]));
+            }
+        } else if token_start.is_none() {
+            token_start = Some((byte, column));
         }
     }
+    if let Some((start, start_column)) = token_start {
+        tokens.push((start_column + 1, &s[start..]));
+    }
+
+    tokens
 }
 
 impl FromStr for Dependency {
-    type Err = String;
+    type Err = ParseDependencyError;
 
+    /// Parses a dependency in the form `from,to:temporal type,temporal direction existential type[,existential direction]`,
+    /// e.g. `a,b:d,f i,b`. See [`crate::ground_truth`] for the meaning of the tokens.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let parts: Vec<&str> = s.split(&[',', ':', ' '][..]).collect();
-        let mut iter = parts.iter();
-        let from = iter
-            .next()
-            .ok_or("Could not parse from activity".to_string())?
-            .to_string();
-        let to = iter
-            .next()
-            .ok_or("Could not parse to activity".to_string())?
-            .to_string();
-
-        // Manually dereference the iterator's items
-        let temporal_dependency = match iter.next().copied() {
-            Some("d") => Some(temporal::DependencyType::Direct),
-            Some("e") => Some(temporal::DependencyType::Eventual),
-            Some("-") => None,
-            Some(s) => panic!("Invalid temporal dependency type {}", s),
-            None => panic!("Missing dependency type"),
+        let end_column = s.chars().count() + 1;
+        let mut tokens = tokenize(s).into_iter();
+        let mut next = |expected| {
+            tokens.next().ok_or(ParseDependencyError {
+                column: end_column,
+                token: String::new(),
+                kind: ParseDependencyErrorKind::Missing(expected),
+            })
+        };
+        let error = |(column, token): (usize, &str), kind| ParseDependencyError {
+            column,
+            token: token.to_string(),
+            kind,
+        };
+
+        let from = next("from activity")?.1.to_string();
+        let to = next("to activity")?.1.to_string();
+
+        let token = next("temporal dependency type")?;
+        let temporal_dependency = match token.1 {
+            "d" => Some(temporal::DependencyType::Direct),
+            "e" => Some(temporal::DependencyType::Eventual),
+            "-" => None,
+            _ => return Err(error(token, ParseDependencyErrorKind::InvalidTemporalType)),
         };
 
-        let direction = match iter.next().copied() {
-            Some("b") => Some(temporal::Direction::Backward),
-            Some("f") => Some(temporal::Direction::Forward),
-            Some("-") => None,
-            Some(s) => panic!("Invalid direction {}", s),
-            None => panic!("Missing direction"),
+        let token = next("temporal direction")?;
+        let direction = match token.1 {
+            "b" => Some(temporal::Direction::Backward),
+            "f" => Some(temporal::Direction::Forward),
+            "-" => None,
+            _ => {
+                return Err(error(
+                    token,
+                    ParseDependencyErrorKind::InvalidTemporalDirection,
+                ))
+            }
         };
 
         let temporal_dependency =
@@ -98,28 +299,42 @@ impl FromStr for Dependency {
                 None
             };
 
-        let existential_dependency = match iter.next().copied() {
-            Some("i") => Some(existential::DependencyType::Implication),
-            Some("e") => Some(existential::DependencyType::Equivalence),
-            Some("ne") => Some(existential::DependencyType::NegatedEquivalence),
-            Some("n") => Some(existential::DependencyType::Nand),
-            Some("o") => Some(existential::DependencyType::Or),
-            Some("-") => None,
-            Some(s) => panic!("Invalid existential dependency type {}", s),
-            None => panic!("Missing existential dependency type"),
+        let token = next("existential dependency type")?;
+        let existential_dependency = match token.1 {
+            "i" => Some(existential::DependencyType::Implication),
+            "e" => Some(existential::DependencyType::Equivalence),
+            "ne" => Some(existential::DependencyType::NegatedEquivalence),
+            "n" => Some(existential::DependencyType::Nand),
+            "o" => Some(existential::DependencyType::Or),
+            "a" => Some(existential::DependencyType::Always),
+            "s" => Some(existential::DependencyType::Sometimes),
+            "-" => None,
+            _ => {
+                return Err(error(
+                    token,
+                    ParseDependencyErrorKind::InvalidExistentialType,
+                ))
+            }
         };
 
-        let direction = match iter.next().copied() {
-            Some("f") => Some(existential::Direction::Forward),
-            Some("b") => Some(existential::Direction::Backward),
-            Some("-") => None,
-            None => {
-                //println!("Assuming both directions");
-                Some(existential::Direction::Both)
+        // the existential direction is optional and defaults to both directions
+        let direction = match next("existential direction").ok() {
+            Some((_, "f")) => Some(existential::Direction::Forward),
+            Some((_, "b")) => Some(existential::Direction::Backward),
+            Some((_, "-")) => None,
+            None => Some(existential::Direction::Both),
+            Some(token) => {
+                return Err(error(
+                    token,
+                    ParseDependencyErrorKind::InvalidExistentialDirection,
+                ))
             }
-            Some(s) => panic!("Invalid direction {}", s),
         };
 
+        if let Ok(token) = next("end of line") {
+            return Err(error(token, ParseDependencyErrorKind::UnexpectedToken));
+        }
+
         let existential_dependency =
             if let (Some(dep), Some(dir)) = (existential_dependency, direction) {
                 Some(existential::ExistentialDependency::new(
@@ -141,16 +356,16 @@ impl FromStr for Dependency {
     }
 }
 
+/// Parses one dependency per non-empty line of `s`, see `Dependency::from_str`.
+///
+/// # Panics
+/// Panics if a line is not a valid dependency.
+#[deprecated(note = "use `ground_truth::parse_ground_truth`, which reports invalid lines")]
 pub fn convert_to_dependencies(s: &str) -> Vec<Dependency> {
-    let mut deps = Vec::new();
-    for line in s.lines() {
-        if line.is_empty() {
-            continue;
-        }
-        let dep = Dependency::from_str(line).unwrap();
-        deps.push(dep);
-    }
-    deps
+    s.lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| Dependency::from_str(line).unwrap())
+        .collect()
 }
 
 #[cfg(test)]
@@ -207,6 +422,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_from_str_extra_whitespace() {
+        let d = Dependency::from_str("  a,b:-,-   ne").unwrap();
+        assert_eq!(d.from, "a");
+        assert_eq!(d.temporal_dependency, None);
+        assert_eq!(
+            d.existential_dependency.unwrap().dependency_type,
+            existential::DependencyType::NegatedEquivalence
+        );
+    }
+
+    #[test]
+    fn test_from_str_invalid_token() {
+        let err = Dependency::from_str("a,b:d,x i,b").unwrap_err();
+        assert_eq!(
+            err,
+            ParseDependencyError {
+                column: 7,
+                token: "x".to_string(),
+                kind: ParseDependencyErrorKind::InvalidTemporalDirection,
+            }
+        );
+        assert_eq!(err.to_string(), "column 7: invalid temporal direction `x`");
+
+        let err = Dependency::from_str("a,b:d,f i,b z").unwrap_err();
+        assert_eq!(err.column, 13);
+        assert_eq!(err.kind, ParseDependencyErrorKind::UnexpectedToken);
+    }
+
+    #[test]
+    fn test_from_cell() {
+        for cell in ["≺d,<=", "≻,⇎", "-,∨", "≺,-", "-,∀", "≺d,∃", "None"] {
+            let d = Dependency::from_cell("a", "b", cell).unwrap();
+            assert_eq!(d.to_string(), cell);
+        }
+
+        let err = Dependency::from_cell("a", "b", "≺d,?").unwrap_err();
+        assert_eq!((err.column, err.token.as_str()), (4, "?"));
+        assert_eq!(err.kind, ParseDependencyErrorKind::InvalidExistentialType);
+    }
+
+    #[test]
+    fn test_from_str_missing_token() {
+        let err = Dependency::from_str("a,b:d,f").unwrap_err();
+        assert_eq!(err.column, 8);
+        assert_eq!(
+            err.to_string(),
+            "column 8: missing existential dependency type"
+        );
+    }
+
     const DEPS_1: &str = "
 a,b:d,f i,b
 a,c:e,f i,b
@@ -215,6 +481,7 @@ a,e:d,f i,b
 ";
 
     #[test]
+    #[allow(deprecated)]
     fn test_convert_to_dependencies() {
         let deps = convert_to_dependencies(DEPS_1);
         assert_eq!(deps.len(), 4);
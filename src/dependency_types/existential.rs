@@ -1,9 +1,23 @@
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use crate::variants::{Activities, Variants};
+
+/// An existential dependency between two activities.
+///
+/// Besides its type and direction, a discovered dependency carries the observations it is based
+/// on: `support` is the number of traces the relation was checked on and `confidence` the ratio
+/// of those traces that satisfy it. Dependencies that were not discovered from an event log, e.g.
+/// ground truths, have a support of 0 and a confidence of 1. The scores are ignored when
+/// comparing dependencies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExistentialDependency {
     pub from: String,
     pub to: String,
     pub dependency_type: DependencyType,
     pub direction: Direction,
+    pub support: usize,
+    pub confidence: f64,
 }
 
 impl ExistentialDependency {
@@ -18,8 +32,42 @@ impl ExistentialDependency {
             to: to.to_string(),
             dependency_type,
             direction,
+            support: 0,
+            confidence: 1.0,
         }
     }
+
+    /// Sets the number of traces the dependency was checked on and the ratio of them that
+    /// satisfy it.
+    pub fn with_scores(mut self, support: usize, confidence: f64) -> Self {
+        self.support = support;
+        self.confidence = confidence;
+        self
+    }
+
+    fn key(&self) -> (&str, &str, &DependencyType, &Direction) {
+        (&self.from, &self.to, &self.dependency_type, &self.direction)
+    }
+}
+
+impl PartialEq for ExistentialDependency {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for ExistentialDependency {}
+
+impl PartialOrd for ExistentialDependency {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ExistentialDependency {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
 }
 
 impl std::fmt::Display for ExistentialDependency {
@@ -28,7 +76,8 @@ impl std::fmt::Display for ExistentialDependency {
             match &self.direction {
                 Direction::Forward => write!(f, "=>"),
                 Direction::Backward => write!(f, "<="),
-                Direction::Both => panic!("Invalid direction for Implication"),
+                // e.g. a ground truth without existential direction, which implies both ways
+                Direction::Both => write!(f, "⇔"),
             }
         } else {
             write!(f, "{}", self.dependency_type)
@@ -36,7 +85,7 @@ impl std::fmt::Display for ExistentialDependency {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Direction {
     Forward,
     Backward,
@@ -44,13 +93,17 @@ pub enum Direction {
 }
 
 #[allow(dead_code)]
-#[derive(PartialEq, Eq, Debug, Clone, PartialOrd, Ord)]
+#[derive(PartialEq, Eq, Debug, Clone, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum DependencyType {
     Implication,
     Equivalence,
     NegatedEquivalence,
     Nand,
     Or,
+    /// Self-relation of an activity that occurs in every trace
+    Always,
+    /// Self-relation of an activity that occurs in some traces, but not in every trace
+    Sometimes,
 }
 
 impl std::fmt::Display for DependencyType {
@@ -61,17 +114,27 @@ impl std::fmt::Display for DependencyType {
             DependencyType::NegatedEquivalence => write!(f, "⇎"),
             DependencyType::Nand => write!(f, "⊼"),
             DependencyType::Or => write!(f, "∨"),
+            DependencyType::Always => write!(f, "∀"),
+            DependencyType::Sometimes => write!(f, "∃"),
         }
     }
 }
 
-// TODO: NAND and OR dependencies
 /// Checks for an existential dependency between two activities within a set of traces.
 ///
 /// This function analyzes the given traces to determine if there is an existential dependency
 /// between the `from` and `to` activities based on the specified threshold. It considers
-/// implications, equivalences, and negated equivalences to identify the type and direction
-/// of the dependency (in that order).
+/// implications, equivalences, negated equivalences, NAND and OR relations to identify the type
+/// and direction of the dependency (in that order). Since the relations are checked in this
+/// order, a NAND is only reported if the pair is not also a negated equivalence, and an OR only
+/// if neither activity implies the other.
+///
+/// NAND and OR relations are symmetric and are therefore always reported with `Direction::Both`.
+///
+/// If `from` and `to` are the same activity, the self-relation is `Always` if the activity occurs
+/// in every trace and `Sometimes` if it is optional, both with `Direction::Both`. Its confidence
+/// is the ratio of traces that contain the activity for `Always`, and of the traces that do not
+/// contain it for `Sometimes`. An activity that occurs in no trace has no self-relation.
 ///
 /// # Arguments
 ///
@@ -88,119 +151,266 @@ pub fn check_existential_dependency(
     to: &str,
     traces: &[Vec<&str>],
     threshold: f64,
+) -> Option<ExistentialDependency> {
+    let variants = Variants::from_traces(traces);
+    check_existential_dependency_in_co_occurrences(
+        from,
+        to,
+        &CoOccurrences::new(&variants),
+        threshold,
+    )
+}
+
+/// Checks for an existential dependency between two activities from the co-occurrence counts of
+/// a log, see `check_existential_dependency`.
+pub fn check_existential_dependency_in_co_occurrences(
+    from: &str,
+    to: &str,
+    traces: &CoOccurrences,
+    threshold: f64,
 ) -> Option<ExistentialDependency> {
     assert!(
         (0.0..=1.0).contains(&threshold),
         "Threshold must be between 0 and 1"
     );
 
-    // if from == to {
-    //     // first check if >2
-    //     if traces.len() > 2 {
-    //         // if even: equivalence
-    //         if traces.len() % 2 == 0 {
-    //             return Some(ExistentialDependency {
-    //                 from: from.to_string(),
-    //                 to: to.to_string(),
-    //                 dependency_type: DependencyType::Equivalence,
-    //                 direction: Direction::Both,
-    //             });
-    //         }
-    //         // if odd: implication
-    //         return Some(ExistentialDependency {
-    //             from: from.to_string(),
-    //             to: to.to_string(),
-    //             dependency_type: DependencyType::Implication,
-    //             direction: Direction::Forward,
-    //         });
-    //     }
-    // }
-
-    // if from == to {
-    // TODO: instead of traces.len(), we should use the number of from activities in traces
-    // }
+    if from == to {
+        let occurrence = occurrence_ratio(from, traces);
+        if occurrence.valid == 0 {
+            return None;
+        }
+        let (ratio, dependency_type) = if occurrence.holds(threshold) {
+            (occurrence, DependencyType::Always)
+        } else {
+            let absence = Ratio {
+                valid: occurrence.total - occurrence.valid,
+                total: occurrence.total,
+            };
+            (absence, DependencyType::Sometimes)
+        };
+        return Some(ratio.score(ExistentialDependency::new(
+            from,
+            to,
+            dependency_type,
+            Direction::Both,
+        )));
+    }
 
-    let implication = has_implication(from, to, traces, threshold);
+    let implication = implication_ratio(from, to, traces);
+    let reverse_implication = implication_ratio(to, from, traces);
 
-    if implication || has_implication(to, from, traces, threshold) {
-        return Some(ExistentialDependency {
-            from: from.to_string(),
-            to: to.to_string(),
-            dependency_type: if implication && has_implication(to, from, traces, threshold) {
-                DependencyType::Equivalence
+    let implication_dependency = match (
+        implication.holds(threshold),
+        reverse_implication.holds(threshold),
+    ) {
+        // an equivalence is only as strong as the weaker of its two implications
+        (true, true) => Some((
+            if implication.confidence() <= reverse_implication.confidence() {
+                implication
             } else {
-                DependencyType::Implication
+                reverse_implication
             },
-            direction: if implication {
-                Direction::Forward
-            } else {
-                Direction::Backward
-            },
-        });
+            DependencyType::Equivalence,
+            Direction::Forward,
+        )),
+        (true, false) => Some((implication, DependencyType::Implication, Direction::Forward)),
+        (false, true) => Some((
+            reverse_implication,
+            DependencyType::Implication,
+            Direction::Backward,
+        )),
+        (false, false) => None,
+    };
+
+    if let Some((ratio, dependency_type, direction)) = implication_dependency {
+        return Some(ratio.score(ExistentialDependency::new(
+            from,
+            to,
+            dependency_type,
+            direction,
+        )));
     }
 
-    let negated_equivalence = negated_equivalence(from, to, traces, threshold);
+    let negated_equivalence = negated_equivalence_ratio(from, to, traces);
 
-    if negated_equivalence {
-        return Some(ExistentialDependency {
-            from: from.to_string(),
-            to: to.to_string(),
-            dependency_type: DependencyType::NegatedEquivalence,
-            direction: Direction::Forward,
-        });
+    if negated_equivalence.holds(threshold) {
+        return Some(negated_equivalence.score(ExistentialDependency::new(
+            from,
+            to,
+            DependencyType::NegatedEquivalence,
+            Direction::Forward,
+        )));
+    }
+
+    let nand = nand_ratio(from, to, traces);
+
+    if nand.holds(threshold) {
+        return Some(nand.score(ExistentialDependency::new(
+            from,
+            to,
+            DependencyType::Nand,
+            Direction::Both,
+        )));
+    }
+
+    let or = or_ratio(from, to, traces);
+
+    if or.holds(threshold) {
+        return Some(or.score(ExistentialDependency::new(
+            from,
+            to,
+            DependencyType::Or,
+            Direction::Both,
+        )));
     }
 
     None
 }
 
+/// The number of traces containing each pair of activities, from which the existential
+/// relations of every pair are derived without scanning the traces again.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CoOccurrences<'a> {
+    activities: Activities<'a>,
+    traces: usize,
+    /// Row-major matrix of the traces containing both activities, with the traces containing
+    /// an activity on the diagonal
+    counts: Vec<usize>,
+}
+
+impl<'a> CoOccurrences<'a> {
+    /// Counts the co-occurrences of all pairs of activities in one pass over the activity sets
+    /// of the variants.
+    pub fn new(variants: &Variants<'a>) -> Self {
+        let activities = variants.activities().clone();
+        let n = activities.len();
+        let mut counts = vec![0; n * n];
+        for variant in variants.iter() {
+            let present: Vec<usize> = variant.presence.iter().collect();
+            for &from in &present {
+                for &to in &present {
+                    counts[from * n + to] += variant.count;
+                }
+            }
+        }
+        CoOccurrences {
+            activities,
+            traces: variants.traces(),
+            counts,
+        }
+    }
+
+    /// Number of traces containing both activities.
+    fn both(&self, from: &str, to: &str) -> usize {
+        match (self.activities.id(from), self.activities.id(to)) {
+            (Some(from), Some(to)) => self.counts[from * self.activities.len() + to],
+            _ => 0,
+        }
+    }
+
+    /// Number of traces containing the activity.
+    fn occurrences(&self, activity: &str) -> usize {
+        self.both(activity, activity)
+    }
+}
+
+/// Number of traces that satisfy a relation out of the traces it was checked on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Ratio {
+    valid: usize,
+    total: usize,
+}
+
+impl Ratio {
+    fn confidence(&self) -> f64 {
+        self.valid as f64 / self.total as f64
+    }
+
+    /// Whether the proportion of valid traces is greater than or equal to the threshold.
+    fn holds(&self, threshold: f64) -> bool {
+        self.confidence() >= threshold
+    }
+
+    fn score(&self, dependency: ExistentialDependency) -> ExistentialDependency {
+        dependency.with_scores(self.total, self.confidence())
+    }
+}
+
 /// Checks if there is an implication relationship between two events within a set of event traces.
 ///
 /// # Parameters
 /// - `from`: The event that implies the occurrence of another event.
 /// - `to`: The event that is implied by the occurrence of the `from` event.
-/// - `event_names`: A vector of vectors, where each inner vector represents a sequence of event names (a trace).
-/// - `threshold`: A threshold value between 0 and 1 that determines the minimum proportion of valid traces required to confirm the implication.
+/// - `event_names`: The co-occurrence counts of the events in the traces of the log.
 ///
 /// # Returns
-/// - `true` if the proportion of valid traces is greater than or equal to the threshold, indicating that the implication holds.
-/// - `false` otherwise.
-fn has_implication(from: &str, to: &str, event_names: &[Vec<&str>], threshold: f64) -> bool {
-    let total_traces = event_names.len();
-    let valid_traces = event_names
-        .iter()
-        .filter(|trace| {
-            if trace.contains(&from) {
-                trace.contains(&to)
-            } else {
-                true
-            }
-        })
-        .count();
-    valid_traces as f64 / total_traces as f64 >= threshold
+/// The proportion of traces that either do not contain `from` or contain both events; the
+/// implication holds if it is greater than or equal to the threshold.
+fn implication_ratio(from: &str, to: &str, event_names: &CoOccurrences) -> Ratio {
+    Ratio {
+        valid: event_names.traces - event_names.occurrences(from) + event_names.both(from, to),
+        total: event_names.traces,
+    }
 }
 
-fn negated_equivalence(from: &str, to: &str, event_names: &[Vec<&str>], threshold: f64) -> bool {
-    let filtered_traces: Vec<_> = event_names
-        .iter()
-        .filter(|&trace| trace.contains(&from) || trace.contains(&to))
-        .collect();
-    let valid_traces = filtered_traces
-        .iter()
-        .filter(|trace| {
-            if trace.contains(&from) {
-                !trace.contains(&to)
-            } else {
-                trace.contains(&to)
-            }
-        })
-        .count();
-    valid_traces as f64 / filtered_traces.len() as f64 >= threshold
+/// Checks if exactly one of two events occurs in every trace.
+///
+/// Unlike a NAND, traces that contain neither event do not satisfy a negated equivalence.
+///
+/// # Returns
+/// The proportion of traces that contain exactly one of the events.
+fn negated_equivalence_ratio(from: &str, to: &str, event_names: &CoOccurrences) -> Ratio {
+    let either = event_names.occurrences(from) + event_names.occurrences(to);
+    Ratio {
+        valid: either - 2 * event_names.both(from, to),
+        total: event_names.traces,
+    }
+}
+
+/// Checks if an event occurs in every trace.
+///
+/// # Returns
+/// The proportion of traces containing the event.
+fn occurrence_ratio(event: &str, event_names: &CoOccurrences) -> Ratio {
+    Ratio {
+        valid: event_names.occurrences(event),
+        total: event_names.traces,
+    }
+}
+
+/// Checks if two events never occur together within the same trace.
+///
+/// Unlike a negated equivalence, traces that contain neither event also count as valid traces.
+///
+/// # Returns
+/// The proportion of traces that do not contain both events.
+fn nand_ratio(from: &str, to: &str, event_names: &CoOccurrences) -> Ratio {
+    Ratio {
+        valid: event_names.traces - event_names.both(from, to),
+        total: event_names.traces,
+    }
+}
+
+/// Checks if at least one of two events occurs in every trace.
+///
+/// # Returns
+/// The proportion of traces that contain at least one of the events.
+fn or_ratio(from: &str, to: &str, event_names: &CoOccurrences) -> Ratio {
+    Ratio {
+        valid: event_names.occurrences(from) + event_names.occurrences(to)
+            - event_names.both(from, to),
+        total: event_names.traces,
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn co_occurrences<'a>(traces: &[Vec<&'a str>]) -> CoOccurrences<'a> {
+        CoOccurrences::new(&Variants::from_traces(traces))
+    }
+
     #[test]
     fn test_has_implication() {
         let event_names = vec![
@@ -222,13 +432,14 @@ mod tests {
             ("E", "A"),
             ("E", "D"),
         ];
+        let event_names = co_occurrences(&event_names);
         activities.iter().for_each(|from| {
             activities.iter().for_each(|to| {
                 if from != to {
                     if pairs.contains(&(from, to)) {
-                        assert!(has_implication(from, to, &event_names, 1.0));
+                        assert!(implication_ratio(from, to, &event_names).holds(1.0));
                     } else {
-                        assert!(!has_implication(from, to, &event_names, 1.0));
+                        assert!(!implication_ratio(from, to, &event_names).holds(1.0));
                     }
                 }
             });
@@ -244,23 +455,205 @@ mod tests {
             vec!["A", "D"],
             vec!["A", "C"], // Noise: D is missing
         ];
-        assert!(has_implication("A", "D", &event_names, 0.8));
-        assert!(!has_implication("A", "D", &event_names, 1.0));
+        let event_names = co_occurrences(&event_names);
+        assert!(implication_ratio("A", "D", &event_names).holds(0.8));
+        assert!(!implication_ratio("A", "D", &event_names).holds(1.0));
     }
 
     #[test]
     fn test_same_activity_existential_1() {
         let traces = vec![vec!["A", "B", "C", "C", "A"]];
-        let expected = Some(ExistentialDependency {
-            from: "A".to_string(),
-            to: "A".to_string(),
-            dependency_type: DependencyType::Equivalence,
-            direction: Direction::Forward,
-        });
+        let expected = Some(ExistentialDependency::new(
+            "A",
+            "A",
+            DependencyType::Always,
+            Direction::Both,
+        ));
         let actual = check_existential_dependency("A", "A", &traces, 1.0);
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn test_or() {
+        let traces = vec![
+            vec!["A", "B", "C"],
+            vec!["A", "C", "B"],
+            vec!["D", "A", "B", "C"],
+            vec!["D"],
+        ];
+        let expected = Some(ExistentialDependency::new(
+            "A",
+            "D",
+            DependencyType::Or,
+            Direction::Both,
+        ));
+        assert_eq!(
+            expected,
+            check_existential_dependency("A", "D", &traces, 1.0)
+        );
+        assert_eq!(
+            Some(ExistentialDependency::new(
+                "D",
+                "A",
+                DependencyType::Or,
+                Direction::Both
+            )),
+            check_existential_dependency("D", "A", &traces, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_or_with_noise() {
+        let traces = vec![
+            vec!["A", "D"],
+            vec!["A", "D"],
+            vec!["A", "D"],
+            vec!["A"],
+            vec!["A"],
+            vec!["A"],
+            vec!["D"],
+            vec!["D"],
+            vec!["D"],
+            vec!["B"], // Noise: neither A nor D
+        ];
+        let variants = co_occurrences(&traces);
+        assert!(or_ratio("A", "D", &variants).holds(0.8));
+        assert!(!or_ratio("A", "D", &variants).holds(1.0));
+        assert_eq!(
+            Some(DependencyType::Or),
+            check_existential_dependency("A", "D", &traces, 0.8).map(|d| d.dependency_type)
+        );
+        assert_eq!(None, check_existential_dependency("A", "D", &traces, 1.0));
+    }
+
+    #[test]
+    fn test_nand_with_noise() {
+        let traces = vec![
+            vec!["A", "C"],
+            vec!["A", "C"],
+            vec!["B", "C"],
+            vec!["B", "C"],
+            vec!["C"],
+            vec!["C"],
+            vec!["C"],
+            vec!["C"],
+            vec!["C"],
+            vec!["A", "B", "C"], // Noise: A and B occur together
+        ];
+        let variants = co_occurrences(&traces);
+        assert!(nand_ratio("A", "B", &variants).holds(0.9));
+        assert!(!nand_ratio("A", "B", &variants).holds(1.0));
+        // only 4 of the 10 traces contain exactly one of them
+        assert!(!negated_equivalence_ratio("A", "B", &variants).holds(0.9));
+
+        let expected = Some(ExistentialDependency::new(
+            "A",
+            "B",
+            DependencyType::Nand,
+            Direction::Both,
+        ));
+        assert_eq!(
+            expected,
+            check_existential_dependency("A", "B", &traces, 0.9)
+        );
+        assert_eq!(None, check_existential_dependency("A", "B", &traces, 1.0));
+    }
+
+    #[test]
+    fn test_negated_equivalence_and_nand() {
+        // exactly one of A and B occurs in every trace, C and D never occur together, but some
+        // traces contain neither of them
+        let traces = vec![vec!["A", "C"], vec!["B", "D"], vec!["A"], vec!["B"]];
+        let variants = co_occurrences(&traces);
+        assert!(nand_ratio("A", "B", &variants).holds(1.0));
+        assert!(nand_ratio("C", "D", &variants).holds(1.0));
+        assert!(!negated_equivalence_ratio("C", "D", &variants).holds(0.6));
+
+        let dependency_type = |from, to| {
+            check_existential_dependency(from, to, &traces, 1.0).map(|d| d.dependency_type)
+        };
+        assert_eq!(
+            Some(DependencyType::NegatedEquivalence),
+            dependency_type("A", "B")
+        );
+        assert_eq!(Some(DependencyType::Nand), dependency_type("C", "D"));
+    }
+
+    #[test]
+    fn test_same_activity_optional() {
+        let traces = vec![vec!["A", "B", "A"], vec!["B"], vec!["B"], vec!["A"]];
+        let sometimes = check_existential_dependency("A", "A", &traces, 1.0).unwrap();
+        assert_eq!(
+            sometimes,
+            ExistentialDependency::new("A", "A", DependencyType::Sometimes, Direction::Both)
+        );
+        assert_eq!((sometimes.support, sometimes.confidence), (4, 0.5));
+
+        let always = check_existential_dependency("A", "A", &traces, 0.5).unwrap();
+        assert_eq!(
+            always,
+            ExistentialDependency::new("A", "A", DependencyType::Always, Direction::Both)
+        );
+        assert_eq!((always.support, always.confidence), (4, 0.5));
+
+        assert_eq!(None, check_existential_dependency("X", "X", &traces, 1.0));
+    }
+
+    #[test]
+    fn test_co_occurrences() {
+        let traces = vec![
+            vec!["A", "B", "A"],
+            vec!["A", "B", "A"],
+            vec!["B", "C"],
+            vec!["A"],
+        ];
+        let counts = co_occurrences(&traces);
+        assert_eq!(counts.occurrences("A"), 3);
+        assert_eq!(counts.both("A", "B"), 2);
+        assert_eq!(counts.both("B", "A"), 2);
+        assert_eq!(counts.both("A", "C"), 0);
+        assert_eq!(counts.occurrences("D"), 0);
+    }
+
+    #[test]
+    fn test_scores() {
+        let traces = vec![
+            vec!["A", "B"],
+            vec!["A", "B"],
+            vec!["A", "B"],
+            vec!["A"],
+            vec!["B"],
+        ];
+        // A => B holds in 4 of 5 traces, B => A in 4 of 5
+        let equivalence = check_existential_dependency("A", "B", &traces, 0.8).unwrap();
+        assert_eq!(DependencyType::Equivalence, equivalence.dependency_type);
+        assert_eq!((5, 0.8), (equivalence.support, equivalence.confidence));
+
+        // every trace is considered for a negated equivalence, also those without A and B
+        let traces = vec![
+            vec!["A"],
+            vec!["A"],
+            vec!["A"],
+            vec!["B"],
+            vec!["B"],
+            vec!["B"],
+            vec!["A", "B"],
+            vec!["C"],
+        ];
+        let negated_equivalence = check_existential_dependency("A", "B", &traces, 0.75).unwrap();
+        assert_eq!(
+            DependencyType::NegatedEquivalence,
+            negated_equivalence.dependency_type
+        );
+        assert_eq!(
+            (8, 0.75),
+            (negated_equivalence.support, negated_equivalence.confidence)
+        );
+
+        let occurrence = check_existential_dependency("A", "A", &traces, 0.5).unwrap();
+        assert_eq!((8, 0.5), (occurrence.support, occurrence.confidence));
+    }
+
     // #[test]
     // fn test_same_activity_existential_2() {
     //     let traces = vec![vec!["A", "B", "C", "A", "A"]];
@@ -1,11 +1,23 @@
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+use crate::variants::{Variant, Variants};
+
+/// A temporal dependency between two activities.
+///
+/// Besides its type and direction, a discovered dependency carries the observations it is based
+/// on: `support` is the number of occurrence pairs of the two activities found in the traces and
+/// `confidence` the ratio of those pairs that have the reported direction. Dependencies that were
+/// not discovered from an event log, e.g. ground truths, have a support of 0 and a confidence of
+/// 1. The scores are ignored when comparing dependencies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TemporalDependency {
     pub from: String,
     pub to: String,
     pub dependency_type: DependencyType,
     pub direction: Direction,
+    pub support: usize,
+    pub confidence: f64,
 }
 
 impl TemporalDependency {
@@ -20,8 +32,42 @@ impl TemporalDependency {
             to: to.to_string(),
             dependency_type,
             direction,
+            support: 0,
+            confidence: 1.0,
         }
     }
+
+    /// Sets the number of occurrence pairs the dependency is based on and the ratio of them
+    /// that have its direction.
+    pub fn with_scores(mut self, support: usize, confidence: f64) -> Self {
+        self.support = support;
+        self.confidence = confidence;
+        self
+    }
+
+    fn key(&self) -> (&str, &str, &DependencyType, &Direction) {
+        (&self.from, &self.to, &self.dependency_type, &self.direction)
+    }
+}
+
+impl PartialEq for TemporalDependency {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for TemporalDependency {}
+
+impl PartialOrd for TemporalDependency {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TemporalDependency {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
 }
 
 impl std::fmt::Display for TemporalDependency {
@@ -33,13 +79,13 @@ impl std::fmt::Display for TemporalDependency {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum Direction {
     Forward,
     Backward,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum DependencyType {
     Direct,
     Eventual,
@@ -56,13 +102,23 @@ impl std::fmt::Display for DependencyType {
 
 /// Checks for temporal dependencies between two activities across multiple traces.
 ///
+/// If `from` and `to` are the same activity, the result describes how the activity repeats
+/// within a trace: a `Direct` dependency if every repetition follows the previous occurrence
+/// immediately (e.g. `A A`), an `Eventual` dependency if other activities occur in between
+/// (e.g. `A B A`), and `None` if the activity never occurs more than once in a trace.
+/// Self-relations always have a `Forward` direction.
+///
 /// # Parameters
 /// - `from`: The starting activity in the dependency.
 /// - `to`: The ending activity in the dependency.
 /// - `traces`: A list of traces where each trace is an ordered sequence of activities.
 /// - `threshold`: The ratio threshold for considering the dependency direction.
-///    (for example, a threshold of 0.8 would mean that the dependency would be considered
-///    a Direct dependency if it is found in at least 80% of the traces)
+///   (for example, a threshold of 0.8 would mean that the dependency would be considered
+///   a Forward dependency if at least 80% of the occurrence pairs are in forward direction)
+/// - `direct_threshold`: The ratio threshold for considering the dependency direct.
+///   (for example, a threshold of 0.8 would mean that the dependency would be considered
+///   a Direct dependency if at least 80% of the occurrence pairs in the direction of the
+///   dependency directly follow each other, and an Eventual dependency otherwise)
 ///
 /// # Returns
 /// An `Option` containing the `TemporalDependency` if a dependency is found; otherwise, `None`.
@@ -71,22 +127,57 @@ pub fn check_temporal_dependency(
     to: &str,
     traces: &[Vec<&str>],
     threshold: f64,
+    direct_threshold: f64,
 ) -> Option<TemporalDependency> {
-    //println!("Checking temporal dependency for {} -> {}", from, to);
-    let mut dependencies = Vec::new();
-
-    for (_, trace) in traces.iter().enumerate() {
-        //println!("Checking trace {}: {:?}", i, trace);
-        let trace_deps = check_trace_dependency(from, to, trace);
-        //println!("{trace_deps:?}");
-        //println!("Trace {} dependencies: {:?}", i, trace_deps);
-        dependencies.extend(trace_deps);
+    check_temporal_dependency_in_variants(
+        from,
+        to,
+        &Variants::from_traces(traces),
+        threshold,
+        direct_threshold,
+    )
+}
+
+/// Checks for temporal dependencies between two activities across the variants of a log, see
+/// `check_temporal_dependency`. The occurrence pairs of a variant count once for each of its
+/// traces.
+pub fn check_temporal_dependency_in_variants(
+    from: &str,
+    to: &str,
+    variants: &Variants,
+    threshold: f64,
+    direct_threshold: f64,
+) -> Option<TemporalDependency> {
+    let mut counts = PairCounts::default();
+
+    for variant in variants.iter() {
+        for (dependency_type, direction) in check_trace_dependency(from, to, variant) {
+            counts.total += variant.count;
+            let direct = dependency_type == DependencyType::Direct;
+            match direction {
+                Direction::Forward => {
+                    counts.forward += variant.count;
+                    if direct {
+                        counts.direct_forward += variant.count;
+                    }
+                }
+                Direction::Backward if direct => counts.direct_backward += variant.count,
+                Direction::Backward => {}
+            }
+        }
     }
 
-    //println!("All dependencies: {:?}", dependencies);
-    let result = classify_dependencies(from, to, dependencies, threshold);
-    //println!("Final result: {:?}", result);
-    result
+    classify_dependencies(from, to, counts, threshold, direct_threshold)
+}
+
+/// Number of occurrence pairs of two activities, of those in forward direction, and of those
+/// directly following each other in either direction.
+#[derive(Debug, Default)]
+struct PairCounts {
+    total: usize,
+    forward: usize,
+    direct_forward: usize,
+    direct_backward: usize,
 }
 
 /// Checks the dependencies between two activities within a single trace.
@@ -94,7 +185,8 @@ pub fn check_temporal_dependency(
 /// # Parameters
 /// - `from`: The starting activity in the dependency.
 /// - `to`: The ending activity in the dependency.
-/// - `trace`: A single trace (ordered sequence of activities).
+/// - `trace`: A single trace (ordered sequence of activities) with the positions of its
+///   activities.
 ///
 /// # Returns
 /// A vector of tuples where each tuple contains the `DependencyType` and `Direction`.
@@ -104,21 +196,30 @@ pub fn check_temporal_dependency(
 fn check_trace_dependency(
     from: &str,
     to: &str,
-    trace: &[&str],
+    trace: &Variant,
 ) -> Vec<(DependencyType, Direction)> {
-    let mut result = Vec::new();
-    let mut from_positions: Vec<usize> = Vec::new();
-    let mut to_positions: Vec<usize> = Vec::new();
-
-    // get the indexes of each `from` and each `to` activities
-    for (i, activity) in trace.iter().enumerate() {
-        if activity == &from {
-            from_positions.push(i);
-        } else if activity == &to {
-            to_positions.push(i);
-        }
+    // edge case for when `from` and `to` are the same: every occurrence is paired with the
+    // next one
+    if from == to {
+        return trace
+            .positions(from)
+            .windows(2)
+            .map(|pair| {
+                let dependency_type = if pair[1] - pair[0] == 1 {
+                    DependencyType::Direct
+                } else {
+                    DependencyType::Eventual
+                };
+                (dependency_type, Direction::Forward)
+            })
+            .collect();
     }
 
+    let mut result = Vec::new();
+    // the indexes of each `from` and each `to` activities
+    let from_positions = trace.positions(from);
+    let to_positions = trace.positions(to);
+
     if from_positions.is_empty() || to_positions.is_empty() {
         return vec![];
     }
@@ -126,17 +227,6 @@ fn check_trace_dependency(
     let mut from_index = 0;
     let mut to_index = 0;
 
-    // edge case for when `from` and `to` are the same
-    if from == to {
-        // check >2
-        if from_positions.len() > 2 {
-            result.push((DependencyType::Eventual, Direction::Forward));
-        } else if from_positions[0] + 1 == from_positions[1] {
-            // check if activity in between
-            result.push((DependencyType::Direct, Direction::Forward));
-        }
-    }
-
     // iterate through the `from` and `to` positions except for the last one
     while from_index < from_positions.len() && to_index < to_positions.len() {
         let from_pos = from_positions[from_index];
@@ -170,7 +260,7 @@ fn check_trace_dependency(
     while from_index < from_positions.len() {
         if to_positions
             .last()
-            .map_or(false, |&last_to| last_to > from_positions[from_index])
+            .is_some_and(|&last_to| last_to > from_positions[from_index])
         {
             result.push((DependencyType::Eventual, Direction::Forward));
         }
@@ -181,7 +271,7 @@ fn check_trace_dependency(
     while to_index < to_positions.len() {
         if from_positions
             .last()
-            .map_or(false, |&last_from| last_from < to_positions[to_index])
+            .is_some_and(|&last_from| last_from < to_positions[to_index])
         {
             result.push((DependencyType::Eventual, Direction::Forward));
         } else {
@@ -198,61 +288,74 @@ fn check_trace_dependency(
 /// # Parameters
 /// - `from`: The starting activity in the dependency.
 /// - `to`: The ending activity in the dependency.
-/// - `dependencies`: A vector of dependencies found in the traces.
+/// - `counts`: The number of occurrence pairs found in the traces, by direction and type.
 /// - `threshold`: The ratio threshold for determining the direction of the dependency.
+/// - `direct_threshold`: The ratio threshold of direct occurrence pairs among the pairs in the
+///   direction of the dependency for determining whether the dependency is direct or eventual.
+///   Pairs in the other direction, e.g. backward noise of a forward dependency, do not count.
 ///
 /// # Returns
 /// An `Option` containing the `TemporalDependency` if a dependency direction meets the threshold; otherwise, `None`.
+/// The dependency's support is the number of occurrence pairs and its confidence the ratio of its direction.
 fn classify_dependencies(
     from: &str,
     to: &str,
-    dependencies: Vec<(DependencyType, Direction)>,
+    counts: PairCounts,
     threshold: f64,
+    direct_threshold: f64,
 ) -> Option<TemporalDependency> {
-    if dependencies.is_empty() {
+    if counts.total == 0 {
         return None;
     }
 
-    let total_count = dependencies.len() as f64;
-    let forward_count = dependencies
-        .iter()
-        .filter(|(_, dir)| *dir == Direction::Forward)
-        .count() as f64;
+    let total_count = counts.total as f64;
+    let forward_count = counts.forward as f64;
     let backward_count = total_count - forward_count;
 
     let forward_ratio = forward_count / total_count;
     let backward_ratio = backward_count / total_count;
 
-    let direction = if forward_ratio >= threshold {
-        Direction::Forward
+    let (direction, confidence, direction_count, direct_count) = if forward_ratio >= threshold {
+        (
+            Direction::Forward,
+            forward_ratio,
+            forward_count,
+            counts.direct_forward as f64,
+        )
     } else if backward_ratio >= threshold {
-        Direction::Backward
+        (
+            Direction::Backward,
+            backward_ratio,
+            backward_count,
+            counts.direct_backward as f64,
+        )
     } else {
         return None; // if neither direction meets the threshold, it's independent
     };
 
-    let dependency_type = if dependencies
-        .iter()
-        .any(|(dep, _)| *dep == DependencyType::Eventual)
-    {
-        DependencyType::Eventual
-    } else {
+    let dependency_type = if direct_count / direction_count >= direct_threshold {
         DependencyType::Direct
+    } else {
+        DependencyType::Eventual
     };
 
-    Some(TemporalDependency::new(
-        from,
-        to,
-        dependency_type,
-        direction,
-    ))
+    Some(
+        TemporalDependency::new(from, to, dependency_type, direction)
+            .with_scores(counts.total, confidence),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::variants::Activities;
     use std::collections::HashMap;
 
+    fn trace_dependency(from: &str, to: &str, trace: &[&str]) -> Vec<(DependencyType, Direction)> {
+        let variant = Variant::new(trace.to_vec(), 1, &mut Activities::default());
+        check_trace_dependency(from, to, &variant)
+    }
+
     #[test]
     fn test_with_loops_general() {
         let event_names = vec![
@@ -264,26 +367,28 @@ mod tests {
 
         pairs_and_deps.insert(
             ("A", "B"),
-            check_temporal_dependency("A", "B", &event_names, 1.0),
+            check_temporal_dependency("A", "B", &event_names, 1.0, 1.0),
         );
         pairs_and_deps.insert(
             ("A", "C"),
-            check_temporal_dependency("A", "C", &event_names, 1.0),
+            check_temporal_dependency("A", "C", &event_names, 1.0, 1.0),
         );
         pairs_and_deps.insert(
             ("B", "C"),
-            check_temporal_dependency("B", "C", &event_names, 1.0),
+            check_temporal_dependency("B", "C", &event_names, 1.0, 1.0),
         );
         pairs_and_deps.insert(
             ("C", "D"),
-            check_temporal_dependency("C", "D", &event_names, 1.0),
+            check_temporal_dependency("C", "D", &event_names, 1.0, 1.0),
         );
         pairs_and_deps.insert(
             ("B", "D"),
-            check_temporal_dependency("B", "D", &event_names, 1.0),
+            check_temporal_dependency("B", "D", &event_names, 1.0, 1.0),
+        );
+        pairs_and_deps.insert(
+            ("B", "B"),
+            check_temporal_dependency("B", "B", &event_names, 1.0, 1.0),
         );
-        // FIXME: fix me :3
-        // pairs_and_deps.insert(("B", "B"), check_temporal_dependency("B", "B", &event_names, 1.0));
 
         let expected = HashMap::from([
             (("A", "B"), None),
@@ -339,7 +444,7 @@ mod tests {
             (DependencyType::Eventual, Direction::Forward),
             (DependencyType::Direct, Direction::Forward),
         ];
-        assert_eq!(expected, check_trace_dependency("A", "C", trace));
+        assert_eq!(expected, trace_dependency("A", "C", trace));
 
         let expected = Some(TemporalDependency::new(
             "A",
@@ -347,7 +452,7 @@ mod tests {
             DependencyType::Eventual,
             Direction::Forward,
         ));
-        let actual = check_temporal_dependency("A", "C", &traces, 1.0);
+        let actual = check_temporal_dependency("A", "C", &traces, 1.0, 1.0);
         assert_eq!(expected, actual);
     }
 
@@ -359,9 +464,9 @@ mod tests {
             (DependencyType::Eventual, Direction::Forward),
             (DependencyType::Direct, Direction::Backward),
         ];
-        assert_eq!(expected, check_trace_dependency("A", "C", trace));
+        assert_eq!(expected, trace_dependency("A", "C", trace));
 
-        let actual = check_temporal_dependency("A", "C", &traces, 1.0);
+        let actual = check_temporal_dependency("A", "C", &traces, 1.0, 1.0);
         assert_eq!(None, actual);
     }
 
@@ -373,9 +478,9 @@ mod tests {
             (DependencyType::Direct, Direction::Forward),
             (DependencyType::Eventual, Direction::Forward),
         ];
-        assert_eq!(expected, check_trace_dependency("A", "C", trace));
+        assert_eq!(expected, trace_dependency("A", "C", trace));
 
-        let actual = check_temporal_dependency("A", "C", &traces, 1.0);
+        let actual = check_temporal_dependency("A", "C", &traces, 1.0, 1.0);
         let expected = Some(TemporalDependency::new(
             "A",
             "C",
@@ -392,9 +497,9 @@ mod tests {
             (DependencyType::Direct, Direction::Backward),
             (DependencyType::Direct, Direction::Forward),
         ];
-        assert_eq!(expected, check_trace_dependency("A", "C", &traces[0]));
+        assert_eq!(expected, trace_dependency("A", "C", &traces[0]));
 
-        let actual = check_temporal_dependency("A", "C", &traces, 1.0);
+        let actual = check_temporal_dependency("A", "C", &traces, 1.0, 1.0);
         assert_eq!(None, actual);
     }
 
@@ -407,7 +512,7 @@ mod tests {
             DependencyType::Eventual,
             Direction::Forward,
         ));
-        let actual = check_temporal_dependency("A", "A", &traces, 1.0);
+        let actual = check_temporal_dependency("A", "A", &traces, 1.0, 1.0);
         assert_eq!(expected, actual);
     }
 
@@ -420,10 +525,99 @@ mod tests {
             DependencyType::Direct,
             Direction::Forward,
         ));
-        let actual = check_temporal_dependency("A", "A", &traces, 1.0);
+        let actual = check_temporal_dependency("A", "A", &traces, 1.0, 1.0);
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn test_same_activity_trace_dependency() {
+        let trace = vec!["A", "A", "B", "A", "C", "A", "A"];
+        let expected = vec![
+            (DependencyType::Direct, Direction::Forward),
+            (DependencyType::Eventual, Direction::Forward),
+            (DependencyType::Eventual, Direction::Forward),
+            (DependencyType::Direct, Direction::Forward),
+        ];
+        assert_eq!(expected, trace_dependency("A", "A", &trace));
+        assert!(trace_dependency("B", "B", &trace).is_empty());
+    }
+
+    #[test]
+    fn test_same_activity_never_repeated() {
+        let traces = vec![vec!["A", "B", "C"], vec!["C", "A"]];
+        assert_eq!(None, check_temporal_dependency("A", "A", &traces, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_scores() {
+        let traces = vec![
+            vec!["A", "B"],
+            vec!["A", "C", "B"],
+            vec!["A", "B", "A"],
+            vec!["B", "A"],
+            vec!["B", "A"],
+        ];
+        let actual = check_temporal_dependency("A", "B", &traces, 0.6, 1.0).unwrap();
+        assert_eq!(
+            TemporalDependency::new("A", "B", DependencyType::Eventual, Direction::Forward),
+            actual
+        );
+        assert_eq!(5, actual.support);
+        assert_eq!(0.6, actual.confidence);
+
+        // the second A of `A B A` is paired with the B when looking from B
+        assert_eq!(None, check_temporal_dependency("B", "A", &traces, 0.6, 1.0));
+        let reverse = check_temporal_dependency("B", "A", &traces, 0.5, 1.0).unwrap();
+        assert_eq!(Direction::Forward, reverse.direction);
+        assert_eq!((6, 0.5), (reverse.support, reverse.confidence));
+
+        let unobserved =
+            TemporalDependency::new("A", "B", DependencyType::Direct, Direction::Forward);
+        assert_eq!((0, 1.0), (unobserved.support, unobserved.confidence));
+    }
+
+    #[test]
+    fn test_direct_threshold() {
+        let traces = vec![
+            vec!["A", "B"],
+            vec!["A", "B"],
+            vec!["A", "B"],
+            vec!["A", "C", "B"], // Noise: C between A and B
+        ];
+        let direct = TemporalDependency::new("A", "B", DependencyType::Direct, Direction::Forward);
+        let eventual =
+            TemporalDependency::new("A", "B", DependencyType::Eventual, Direction::Forward);
+
+        assert_eq!(
+            Some(eventual),
+            check_temporal_dependency("A", "B", &traces, 1.0, 1.0)
+        );
+        assert_eq!(
+            Some(direct),
+            check_temporal_dependency("A", "B", &traces, 1.0, 0.75)
+        );
+        assert_eq!(
+            Some(DependencyType::Eventual),
+            check_temporal_dependency("A", "B", &traces, 1.0, 0.8).map(|d| d.dependency_type)
+        );
+    }
+
+    #[test]
+    fn test_direct_threshold_ignores_other_direction() {
+        let traces = vec![
+            vec!["A", "B"],
+            vec!["A", "B"],
+            vec!["A", "B"],
+            vec!["B", "C", "A"], // Noise: B eventually before A
+        ];
+        let direct = TemporalDependency::new("A", "B", DependencyType::Direct, Direction::Forward);
+
+        // every forward pair is direct, the backward pair does not lower the direct ratio
+        let dependency = check_temporal_dependency("A", "B", &traces, 0.75, 1.0).unwrap();
+        assert_eq!(direct, dependency);
+        assert_eq!((4, 0.75), (dependency.support, dependency.confidence));
+    }
+
     // #[test]
     // fn test_exclusive_choice() {
     //     let traces = vec![vec!["a", "b"], vec!["a", "c"]];
@@ -433,7 +627,7 @@ mod tests {
     //             DependencyType::Direct,
     //             Direction::Forward,
     //     ));
-    //     let actual = check_temporal_dependency("a", "c", &traces, 1.0);
+    //     let actual = check_temporal_dependency("a", "c", &traces, 1.0, 1.0);
     //     assert_eq!(expected, actual);
     // }
 }
@@ -0,0 +1,1488 @@
+pub mod report;
+pub mod sweep;
+
+use crate::{
+    dependency_types::{dependency::Dependency, existential, temporal},
+    generate_adj_matrix_from_traces,
+    ground_truth::{format_ground_truth, parse_ground_truth_or_matrix},
+    matrix::{csv_row, markdown_row, MatrixFormat},
+    parser::{ParseError, XesLog},
+};
+use report::{EvaluationReport, PairResult};
+use sweep::{format_metric, threshold_grid, SweepMetric, ThresholdSweep};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{
+    File, FileReader, HtmlAnchorElement, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement,
+    MouseEvent,
+};
+use yew::prelude::*;
+
+pub enum Msg {
+    AddRelationship,
+    UpdateFrom(String),
+    UpdateTo(String),
+    UpdateTemporal(String),
+    UpdateTemporalDirection(String),
+    UpdateExistential(String),
+    UpdateExistentialDirection(String),
+    FileImport(Option<File>),
+    FileLoaded(Result<String, String>),
+}
+
+#[derive(Clone)]
+pub struct Relationship {
+    from: String,
+    to: String,
+    temporal_type: Option<temporal::DependencyType>,
+    temporal_direction: Option<temporal::Direction>,
+    existential_type: Option<existential::DependencyType>,
+    existential_direction: Option<existential::Direction>,
+}
+
+#[derive(Clone, Default, PartialEq)]
+pub struct RelationInput {
+    pub from: String,
+    pub to: String,
+    pub temporal_type: Option<temporal::DependencyType>,
+    pub temporal_direction: Option<temporal::Direction>,
+    pub existential_type: Option<existential::DependencyType>,
+    pub existential_direction: Option<existential::Direction>,
+}
+
+impl RelationInput {
+    pub fn to_dependency(&self) -> Option<Dependency> {
+        let temporal_dependency = match (
+            self.temporal_type.as_ref(),
+            self.temporal_direction.as_ref(),
+        ) {
+            (Some(t_type), Some(t_dir)) => Some(temporal::TemporalDependency::new(
+                &self.from,
+                &self.to,
+                t_type.clone(),
+                t_dir.clone(),
+            )),
+            _ => None,
+        };
+
+        let existential_dependency = match (
+            self.existential_type.as_ref(),
+            self.existential_direction.as_ref(),
+        ) {
+            (Some(e_type), Some(e_dir)) => Some(existential::ExistentialDependency::new(
+                &self.from,
+                &self.to,
+                e_type.clone(),
+                e_dir.clone(),
+            )),
+            _ => None,
+        };
+
+        // Always return Some with a new Dependency, bc otherwise the
+        // evaluation will fail (?)
+        Some(Dependency::new(
+            self.from.clone(),
+            self.to.clone(),
+            temporal_dependency,
+            existential_dependency,
+        ))
+    }
+}
+
+impl From<&Dependency> for RelationInput {
+    fn from(dependency: &Dependency) -> Self {
+        Self {
+            from: dependency.from.clone(),
+            to: dependency.to.clone(),
+            temporal_type: dependency
+                .temporal_dependency
+                .as_ref()
+                .map(|dep| dep.dependency_type.clone()),
+            temporal_direction: dependency
+                .temporal_dependency
+                .as_ref()
+                .map(|dep| dep.direction.clone()),
+            existential_type: dependency
+                .existential_dependency
+                .as_ref()
+                .map(|dep| dep.dependency_type.clone()),
+            existential_direction: dependency
+                .existential_dependency
+                .as_ref()
+                .map(|dep| dep.direction.clone()),
+        }
+    }
+}
+
+pub struct Evaluation {
+    relationships: Vec<Relationship>,
+    current: Relationship,
+}
+
+impl Component for Evaluation {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            relationships: Vec::new(),
+            current: Relationship {
+                from: String::new(),
+                to: String::new(),
+                temporal_type: None,
+                temporal_direction: None,
+                existential_type: None,
+                existential_direction: None,
+            },
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::AddRelationship => {
+                if !self.current.from.is_empty() && !self.current.to.is_empty() {
+                    self.relationships.push(self.current.clone());
+                    self.current = Relationship {
+                        from: String::new(),
+                        to: String::new(),
+                        temporal_type: None,
+                        temporal_direction: None,
+                        existential_type: None,
+                        existential_direction: None,
+                    };
+                }
+                true
+            }
+            Msg::UpdateFrom(value) => {
+                self.current.from = value;
+                true
+            }
+            Msg::UpdateTo(value) => {
+                self.current.to = value;
+                true
+            }
+            Msg::UpdateTemporal(value) => {
+                self.current.temporal_type = match value.as_str() {
+                    "direct" => Some(temporal::DependencyType::Direct),
+                    "eventual" => Some(temporal::DependencyType::Eventual),
+                    _ => None,
+                };
+                true
+            }
+            Msg::UpdateTemporalDirection(value) => {
+                self.current.temporal_direction = match value.as_str() {
+                    "forward" => Some(temporal::Direction::Forward),
+                    "backward" => Some(temporal::Direction::Backward),
+                    _ => None,
+                };
+                true
+            }
+            Msg::UpdateExistential(value) => {
+                self.current.existential_type = match value.as_str() {
+                    "implication" => Some(existential::DependencyType::Implication),
+                    "equivalence" => Some(existential::DependencyType::Equivalence),
+                    "negated_equivalence" => Some(existential::DependencyType::NegatedEquivalence),
+                    "nand" => Some(existential::DependencyType::Nand),
+                    "or" => Some(existential::DependencyType::Or),
+                    _ => None,
+                };
+                self.current.existential_direction = None; // Reset direction when type changes
+                true
+            }
+            Msg::UpdateExistentialDirection(value) => {
+                self.current.existential_direction = match value.as_str() {
+                    "forward" => Some(existential::Direction::Forward),
+                    "backward" => Some(existential::Direction::Backward),
+                    "both" => Some(existential::Direction::Both),
+                    _ => None,
+                };
+                true
+            }
+            _ => false,
+        }
+    }
+
+    #[allow(clippy::let_unit_value)] // false positive on component tags in `html!`
+    fn view(&self, _ctx: &Context<Self>) -> Html {
+        html! {
+            <RelationForm />
+        }
+    }
+}
+
+#[function_component(RelationForm)]
+pub fn relation_form() -> Html {
+    let current_relation = use_state(RelationInput::default);
+    let relations = use_state(Vec::<RelationInput>::new);
+    let file_content = use_state(|| None::<Vec<u8>>);
+    let evaluation_result = use_state(|| None::<Result<EvaluationReport, String>>);
+    // Index of the relation that is being edited in the form
+    let editing = use_state(|| None::<usize>);
+    let form_revision = use_state(|| 0_usize);
+    let import_error = use_state(|| None::<String>);
+    let pasted = use_state(String::new);
+    let temporal_threshold = use_state(|| 1.0_f64);
+    let existential_threshold = use_state(|| 1.0_f64);
+    let direct_threshold = use_state(|| 1.0_f64);
+    let sweep_result = use_state(|| None::<Result<ThresholdSweep, String>>);
+    let sweep_min = use_state(|| 0.5_f64);
+    let sweep_step = use_state(|| 0.05_f64);
+
+    let onsubmit = {
+        let current = current_relation.clone();
+        let relations_state = relations.clone();
+        let editing = editing.clone();
+        let form_revision = form_revision.clone();
+        Callback::from(move |e: FocusEvent| {
+            e.prevent_default();
+            let mut new_relations = (*relations_state).clone();
+            if !current.from.is_empty() && !current.to.is_empty() {
+                match *editing {
+                    Some(index) if index < new_relations.len() => {
+                        new_relations[index] = (*current).clone()
+                    }
+                    _ => new_relations.push((*current).clone()),
+                }
+                relations_state.set(new_relations);
+                editing.set(None);
+                // Re-create the selects so that they show the reset relation
+                form_revision.set(*form_revision + 1);
+                current.set(RelationInput::default());
+            }
+        })
+    };
+
+    let oncancel = {
+        let current = current_relation.clone();
+        let editing = editing.clone();
+        let form_revision = form_revision.clone();
+        Callback::from(move |_: MouseEvent| {
+            editing.set(None);
+            form_revision.set(*form_revision + 1);
+            current.set(RelationInput::default());
+        })
+    };
+
+    // Replaces the relations with the ones of an imported or pasted ground truth
+    let import_ground_truth = {
+        let relations = relations.clone();
+        let import_error = import_error.clone();
+        let editing = editing.clone();
+        Callback::from(move |content: String| {
+            match parse_ground_truth_or_matrix(&content) {
+                Ok(deps) => {
+                    relations.set(deps.iter().map(RelationInput::from).collect());
+                    import_error.set(None);
+                }
+                Err(e) => import_error.set(Some(e.to_string())),
+            }
+            editing.set(None);
+        })
+    };
+
+    let onimport = {
+        let import_ground_truth = import_ground_truth.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                let import_ground_truth = import_ground_truth.clone();
+                let reader = FileReader::new().unwrap();
+                let reader_clone = reader.clone();
+                let onload = Closure::wrap(Box::new(move |_: web_sys::ProgressEvent| {
+                    if let Some(content) = reader_clone.result().ok().and_then(|r| r.as_string()) {
+                        import_ground_truth.emit(content);
+                    }
+                })
+                    as Box<dyn FnMut(web_sys::ProgressEvent)>);
+                reader.set_onload(Some(onload.as_ref().unchecked_ref()));
+                onload.forget();
+                reader.read_as_text(&file).unwrap();
+            }
+            // allow importing the same file again after editing
+            input.set_value("");
+        })
+    };
+
+    let onpaste_input = {
+        let pasted = pasted.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlTextAreaElement = e.target_unchecked_into();
+            pasted.set(input.value());
+        })
+    };
+
+    let onpaste_import = {
+        let pasted = pasted.clone();
+        Callback::from(move |_: MouseEvent| import_ground_truth.emit((*pasted).clone()))
+    };
+
+    let onexport = {
+        let relations = relations.clone();
+        let import_error = import_error.clone();
+        Callback::from(move |_: MouseEvent| {
+            let deps: Vec<Dependency> =
+                relations.iter().filter_map(|r| r.to_dependency()).collect();
+            if let Err(e) = download_text("ground_truth.txt", &format_ground_truth(&deps)) {
+                import_error.set(Some(format!("Error exporting the ground truth: {:?}", e)));
+            }
+        })
+    };
+
+    // File upload handler
+    let onchange = {
+        let file_content = file_content.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                let file_content = file_content.clone();
+                let reader = FileReader::new().unwrap();
+                let reader_clone = reader.clone();
+                let onload = Closure::wrap(Box::new(move |_: web_sys::ProgressEvent| {
+                    if let Ok(result) = reader_clone.result() {
+                        file_content.set(Some(js_sys::Uint8Array::new(&result).to_vec()));
+                    }
+                })
+                    as Box<dyn FnMut(web_sys::ProgressEvent)>);
+                reader.set_onload(Some(onload.as_ref().unchecked_ref()));
+                onload.forget();
+                reader.read_as_array_buffer(&file).unwrap();
+            }
+        })
+    };
+
+    let evaluate = {
+        let relations_state = relations.clone();
+        let file_content = file_content.clone();
+        let evaluation_result = evaluation_result.clone();
+        let existential_threshold = existential_threshold.clone();
+        let temporal_threshold = temporal_threshold.clone();
+        let direct_threshold = direct_threshold.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(content) = (*file_content).as_ref() {
+                let deps: Vec<Dependency> = (*relations_state)
+                    .iter()
+                    .filter_map(|r| r.to_dependency())
+                    .collect();
+
+                let result = XesLog::from_bytes(content)
+                    .and_then(|log| log.traces())
+                    .map(|traces| {
+                        evaluation_report(
+                            &deps,
+                            traces,
+                            *existential_threshold,
+                            *temporal_threshold,
+                            *direct_threshold,
+                        )
+                    })
+                    .map_err(|e| e.to_string());
+                evaluation_result.set(Some(result));
+            }
+        })
+    };
+
+    let onsweep = {
+        let relations_state = relations.clone();
+        let file_content = file_content.clone();
+        let sweep_result = sweep_result.clone();
+        let sweep_min = sweep_min.clone();
+        let sweep_step = sweep_step.clone();
+        let direct_threshold = direct_threshold.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Some(content) = (*file_content).as_ref() {
+                let deps: Vec<Dependency> = (*relations_state)
+                    .iter()
+                    .filter_map(|r| r.to_dependency())
+                    .collect();
+                let thresholds = threshold_grid(*sweep_min, 1.0, *sweep_step);
+
+                let result = XesLog::from_bytes(content)
+                    .and_then(|log| log.traces())
+                    .map(|traces| {
+                        ThresholdSweep::run(
+                            &[(deps, traces)],
+                            &thresholds,
+                            &thresholds,
+                            *direct_threshold,
+                        )
+                    })
+                    .map_err(|e| e.to_string());
+                sweep_result.set(Some(result));
+            }
+        })
+    };
+
+    // Parses the value of a number input and keeps it if it lies within `min..=1.0`
+    let threshold_input = |state: &UseStateHandle<f64>, min: f64| {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Ok(value) = input.value().parse::<f64>() {
+                if (min..=1.0).contains(&value) {
+                    state.set(value);
+                }
+            }
+        })
+    };
+
+    html! {
+        <div class="container">
+            <h2>{"Create Dependencies"}</h2>
+            <p>{"Enter each pair of activities that you would like to be represented in your adjacency matrix."}</p>
+            <p>{"The matrix will then be evaluated against the uploaded event log."}</p>
+            <p>{"Please note that the evaluation is based on the dependencies you provide here (which means they are the ones that should be correct)."}</p>
+            <form {onsubmit}>
+                // keyed, so that the selects are re-created with the current relation
+                <div>
+                    <div class="relationship-form" key={form_revision.to_string()}>
+                        <input
+                            type="text"
+                            placeholder="From Activity"
+                            value={current_relation.from.clone()}
+                            onchange={
+                                let current = current_relation.clone();
+                                Callback::from(move |e: Event| {
+                                    let input: HtmlInputElement = e.target_unchecked_into();
+                                    let mut new_relation = (*current).clone();
+                                    new_relation.from = input.value();
+                                    current.set(new_relation);
+                                })
+                            }
+                        />
+                        <input
+                            type="text"
+                            placeholder="To Activity"
+                            value={current_relation.to.clone()}
+                            onchange={
+                                let current = current_relation.clone();
+                                Callback::from(move |e: Event| {
+                                    let input: HtmlInputElement = e.target_unchecked_into();
+                                    let mut new_relation = (*current).clone();
+                                    new_relation.to = input.value();
+                                    current.set(new_relation);
+                                })
+                            }
+                        />
+                        <select
+                            id="temporal-type"
+                            onchange={
+                            let current = current_relation.clone();
+                            Callback::from(move |e: Event| {
+                                let select: HtmlInputElement = e.target_unchecked_into();
+                                let mut new_relation = (*current).clone();
+                                new_relation.temporal_type = match select.value().as_str() {
+                                    "direct" => {
+                                        // Set Forward as default direction for Direct
+                                        new_relation.temporal_direction = Some(temporal::Direction::Forward);
+                                        Some(temporal::DependencyType::Direct)
+                                    }
+                                    "eventual" => {
+                                        // Set Forward as default direction for Eventual
+                                        new_relation.temporal_direction = Some(temporal::Direction::Forward);
+                                        Some(temporal::DependencyType::Eventual)
+                                    }
+                                    "independent" => {
+                                        new_relation.temporal_direction = None;
+                                        None
+                                    }
+                                    _ => None,
+                                };
+                                current.set(new_relation);
+                            })
+                        }>
+                            <option value="">{"Select Temporal Dependency"}</option>
+                            <option value="direct" selected={current_relation.temporal_type == Some(temporal::DependencyType::Direct)}>{"Direct"}</option>
+                            <option value="eventual" selected={current_relation.temporal_type == Some(temporal::DependencyType::Eventual)}>{"Eventual"}</option>
+                            <option value="independent">{"Independent"}</option>
+                        </select>
+
+                        {if current_relation.temporal_type.is_some() {
+                            html! {
+                                <select
+                                    id="temporal-direction"
+                                    value={current_relation.temporal_direction.clone().map_or("forward".to_string(), |d| match d {
+                                        temporal::Direction::Forward => "forward".to_string(),
+                                        temporal::Direction::Backward => "backward".to_string(),
+                                    })}
+                                    onchange={
+                                    let current = current_relation.clone();
+                                    Callback::from(move |e: Event| {
+                                        let select: HtmlInputElement = e.target_unchecked_into();
+                                        let mut new_relation = (*current).clone();
+                                        new_relation.temporal_direction = match select.value().as_str() {
+                                            "forward" => Some(temporal::Direction::Forward),
+                                            "backward" => Some(temporal::Direction::Backward),
+                                            _ => None,
+                                        };
+                                        current.set(new_relation);
+                                    })
+                                }>
+                                    <option value="">{"Select Temporal Direction"}</option>
+                                    <option value="forward" selected={current_relation.temporal_direction != Some(temporal::Direction::Backward)}>{"Forward"}</option>
+                                    <option value="backward" selected={current_relation.temporal_direction == Some(temporal::Direction::Backward)}>{"Backward"}</option>
+                                </select>
+                            }
+                        } else {
+                            html! {}
+                        }}
+                        <select
+                            id="existential-type"
+                            onchange={
+                            let current = current_relation.clone();
+                            Callback::from(move |e: Event| {
+                                let select: HtmlInputElement = e.target_unchecked_into();
+                                let mut new_relation = (*current).clone();
+                                new_relation.existential_type = match select.value().as_str() {
+                                    "implication" => {
+                                        new_relation.existential_direction = Some(existential::Direction::Forward);
+                                        Some(existential::DependencyType::Implication)
+                                    },
+                                    "equivalence" | "negated_equivalence" | "nand" | "or" => {
+                                        new_relation.existential_direction = Some(existential::Direction::Both);
+                                        match select.value().as_str() {
+                                            "equivalence" => Some(existential::DependencyType::Equivalence),
+                                            "negated_equivalence" => Some(existential::DependencyType::NegatedEquivalence),
+                                            "nand" => Some(existential::DependencyType::Nand),
+                                            "or" => Some(existential::DependencyType::Or),
+                                            _ => None,
+                                        }
+                                    },
+                                    "independent" => None,
+                                    _ => None,
+                                };
+                                current.set(new_relation);
+                            })
+                        }>
+                            <option value="">{"Select Existential Dependency"}</option>
+                            <option value="implication" selected={current_relation.existential_type == Some(existential::DependencyType::Implication)}>{"Implication"}</option>
+                            <option value="equivalence" selected={current_relation.existential_type == Some(existential::DependencyType::Equivalence)}>{"Equivalence"}</option>
+                            <option value="negated_equivalence" selected={current_relation.existential_type == Some(existential::DependencyType::NegatedEquivalence)}>{"Negated Equivalence"}</option>
+                            <option value="nand" selected={current_relation.existential_type == Some(existential::DependencyType::Nand)}>{"Nand"}</option>
+                            <option value="or" selected={current_relation.existential_type == Some(existential::DependencyType::Or)}>{"Or"}</option>
+                            <option value="independent">{"Independent"}</option>
+                        </select>
+
+                        {if current_relation.existential_type.is_some() &&
+                            matches!(current_relation.existential_type, Some(existential::DependencyType::Implication)) {
+                            html! {
+                                <select
+                                    id="existential-direction"
+                                    value={current_relation.existential_direction.clone().map_or("".to_string(), |d| match d {
+                                        existential::Direction::Forward => "forward".to_string(),
+                                        existential::Direction::Backward => "backward".to_string(),
+                                        existential::Direction::Both => "both".to_string(),
+                                    })}
+                                    onchange={
+                                    let current = current_relation.clone();
+                                    Callback::from(move |e: Event| {
+                                        let select: HtmlInputElement = e.target_unchecked_into();
+                                        let mut new_relation = (*current).clone();
+                                        new_relation.existential_direction = match select.value().as_str() {
+                                            "forward" => Some(existential::Direction::Forward),
+                                            "backward" => Some(existential::Direction::Backward),
+                                            "both" => Some(existential::Direction::Both),
+                                            _ => None,
+                                        };
+                                        current.set(new_relation);
+                                    })
+                                }>
+                                    <option value="">{"Select Existential Direction"}</option>
+                                    <option
+                                        value="forward"
+                                        selected={current_relation.existential_direction != Some(existential::Direction::Backward)}
+                                    >
+                                        {"Forward"}
+                                    </option>
+                                    <option value="backward" selected={current_relation.existential_direction == Some(existential::Direction::Backward)}>{"Backward"}</option>
+                                    <option
+                                        value="both"
+                                        selected={matches!(
+                                            current_relation.existential_type,
+                                            Some(existential::DependencyType::Equivalence) |
+                                            Some(existential::DependencyType::NegatedEquivalence) |
+                                            Some(existential::DependencyType::Nand) |
+                                            Some(existential::DependencyType::Or)
+                                        )}
+                                    >
+                                        {"Both"}
+                                    </option>
+                                </select>
+                            }
+                        } else {
+                            html! {}
+                        }}
+                    </div>
+                </div>
+                <button type="submit" disabled={current_relation.from.is_empty() || current_relation.to.is_empty()} style="margin-top: 10px;">
+                    {if editing.is_some() { "Update Relationship" } else { "Add Relationship" }}
+                </button>
+                {if editing.is_some() {
+                    html! {
+                        <button type="button" onclick={oncancel} style="margin: 10px 0 0 10px;">{"Cancel"}</button>
+                    }
+                } else {
+                    html! {}
+                }}
+            </form>
+
+            // Import and export of the whole ground truth
+            <div class="ground-truth-import" style="margin-top: 20px;">
+                <p>{"Alternatively, import a ground truth file (one relation per line, e.g. `a,b:d,f i,b`), or paste the file content or a CSV matrix as exported by the CLI. Importing replaces the relations below."}</p>
+                <input type="file" accept=".txt,.csv" onchange={onimport} style="display: block; margin: 10px 0;" />
+                <textarea
+                    rows="6"
+                    cols="60"
+                    placeholder="a,b:d,f i,b"
+                    value={(*pasted).clone()}
+                    oninput={onpaste_input}
+                />
+                <div>
+                    <button onclick={onpaste_import} disabled={pasted.trim().is_empty()}>{"Import Pasted Ground Truth"}</button>
+                    <button onclick={onexport} disabled={relations.is_empty()} style="margin-left: 10px;">{"Export Ground Truth"}</button>
+                </div>
+                {if let Some(error) = &*import_error {
+                    html! { <p style="color: #c0392b;">{error}</p> }
+                } else {
+                    html! {}
+                }}
+            </div>
+
+            // Display existing relations
+            <div class="relations-list">
+                <table>
+                    <thead>
+                        <tr>
+                            <th>{"From"}</th>
+                            <th>{"To"}</th>
+                            <th>{"Temporal Type"}</th>
+                            <th>{"Temporal Direction"}</th>
+                            <th>{"Existential Type"}</th>
+                            <th>{"Existential Direction"}</th>
+                            <th></th>
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {for (*relations).iter().enumerate().map(|(index, rel)| {
+                            let onedit = {
+                                let current = current_relation.clone();
+                                let editing = editing.clone();
+                                let form_revision = form_revision.clone();
+                                let rel = rel.clone();
+                                Callback::from(move |_: MouseEvent| {
+                                    current.set(rel.clone());
+                                    editing.set(Some(index));
+                                    form_revision.set(*form_revision + 1);
+                                })
+                            };
+                            let ondelete = {
+                                let relations = relations.clone();
+                                let current = current_relation.clone();
+                                let editing = editing.clone();
+                                let form_revision = form_revision.clone();
+                                Callback::from(move |_: MouseEvent| {
+                                    let mut new_relations = (*relations).clone();
+                                    new_relations.remove(index);
+                                    relations.set(new_relations);
+                                    match *editing {
+                                        // reset the form, so that saving it does not add the
+                                        // deleted relation again
+                                        Some(i) if i == index => {
+                                            editing.set(None);
+                                            form_revision.set(*form_revision + 1);
+                                            current.set(RelationInput::default());
+                                        }
+                                        // indices after the deleted relation have shifted
+                                        Some(i) if i > index => editing.set(Some(i - 1)),
+                                        _ => {}
+                                    }
+                                })
+                            };
+                            html! {
+                                <tr style={if *editing == Some(index) { "background-color: #fff3cd;" } else { "" }}>
+                                    <td>{&rel.from}</td>
+                                    <td>{&rel.to}</td>
+                                    <td>{format!("{:?}", &rel.temporal_type)}</td>
+                                    <td>{format!("{:?}", &rel.temporal_direction)}</td>
+                                    <td>{format!("{:?}", &rel.existential_type)}</td>
+                                    <td>{format!("{:?}", &rel.existential_direction)}</td>
+                                    <td>
+                                        <button onclick={onedit}>{"Edit"}</button>
+                                        <button onclick={ondelete} style="margin-left: 5px;">{"Delete"}</button>
+                                    </td>
+                                </tr>
+                            }
+                        })}
+                    </tbody>
+                </table>
+            </div>
+
+            // File upload and evaluation section
+            <div class="evaluation-section" style="padding-top: 60px;">
+                <p>{"Please upload an event log in .xes or .xes.gz format."}</p>
+                <input
+                    type="file"
+                    accept=".xes,.gz"
+                    onchange={onchange}
+                    style="display: block; margin: 10px 0;"
+                />
+                <div style="margin: 10px 0;">
+                    <label for="evaluation-temporal-threshold">{"Temporal Threshold: "}</label>
+                    <input
+                        id="evaluation-temporal-threshold"
+                        type="number"
+                        min="0"
+                        max="1.0"
+                        step="0.05"
+                        value={temporal_threshold.to_string()}
+                        oninput={threshold_input(&temporal_threshold, 0.0)}
+                        style="width: 70px; margin-right: 20px;"
+                    />
+                    <label for="evaluation-existential-threshold">{"Existential Threshold: "}</label>
+                    <input
+                        id="evaluation-existential-threshold"
+                        type="number"
+                        min="0"
+                        max="1.0"
+                        step="0.05"
+                        value={existential_threshold.to_string()}
+                        oninput={threshold_input(&existential_threshold, 0.0)}
+                        style="width: 70px; margin-right: 20px;"
+                    />
+                    <label for="evaluation-direct-threshold">{"Direct Threshold: "}</label>
+                    <input
+                        id="evaluation-direct-threshold"
+                        type="number"
+                        min="0"
+                        max="1.0"
+                        step="0.05"
+                        value={direct_threshold.to_string()}
+                        oninput={threshold_input(&direct_threshold, 0.0)}
+                        style="width: 70px;"
+                    />
+                </div>
+                <button onclick={evaluate} disabled={file_content.is_none()}>
+                    {"Evaluate Dependencies"}
+                </button>
+
+                // Display evaluation results
+                {match &*evaluation_result {
+                    Some(Ok(report)) => html! {
+                        <div class="evaluation-results">
+                            <h3>{"Evaluation Results"}</h3>
+                            <p>{format!("Temporal Dependencies: {}/{} ({:.0}%)",
+                                report.correct_temporal,
+                                report.total,
+                                (report.correct_temporal as f64 / report.total as f64 * 100.0)
+                            )}</p>
+                            <p>{format!("Existential Dependencies: {}/{} ({:.0}%)",
+                                report.correct_existential,
+                                report.total,
+                                (report.correct_existential as f64 / report.total as f64 * 100.0)
+                            )}</p>
+                            <PairResults report={report.clone()} />
+                        </div>
+                    },
+                    Some(Err(error)) => html! {
+                        <p style="color: #c0392b;">{format!("Error parsing the event log: {}", error)}</p>
+                    },
+                    None => html! {},
+                }}
+            </div>
+
+            // Threshold sweep
+            <div class="sweep-section" style="padding-top: 40px;">
+                <h3>{"Threshold Sweep"}</h3>
+                <p>{"Evaluates the uploaded event log with every combination of temporal and existential thresholds between the lowest threshold and 1.0, using the direct threshold above."}</p>
+                <div style="margin: 10px 0;">
+                    <label for="sweep-min">{"Lowest Threshold: "}</label>
+                    <input
+                        id="sweep-min"
+                        type="number"
+                        min="0"
+                        max="1.0"
+                        step="0.05"
+                        value={sweep_min.to_string()}
+                        oninput={threshold_input(&sweep_min, 0.0)}
+                        style="width: 70px; margin-right: 20px;"
+                    />
+                    <label for="sweep-step">{"Step: "}</label>
+                    <input
+                        id="sweep-step"
+                        type="number"
+                        min="0.01"
+                        max="1.0"
+                        step="0.01"
+                        value={sweep_step.to_string()}
+                        oninput={threshold_input(&sweep_step, 0.01)}
+                        style="width: 70px;"
+                    />
+                </div>
+                <button onclick={onsweep} disabled={file_content.is_none() || relations.is_empty()}>
+                    {"Sweep Thresholds"}
+                </button>
+                {match &*sweep_result {
+                    Some(Ok(sweep)) => html! { <SweepHeatmap sweep={sweep.clone()} /> },
+                    Some(Err(error)) => html! {
+                        <p style="color: #c0392b;">{format!("Error parsing the event log: {}", error)}</p>
+                    },
+                    None => html! {},
+                }}
+            </div>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct SweepHeatmapProps {
+    pub sweep: ThresholdSweep,
+}
+
+/// Shows a metric of a threshold sweep as a heatmap with one row per temporal and one column per
+/// existential threshold, from red (0) to green (1), with the best setting outlined.
+#[function_component(SweepHeatmap)]
+pub fn sweep_heatmap(props: &SweepHeatmapProps) -> Html {
+    let metric = use_state(SweepMetric::default);
+    let export_error = use_state(|| None::<String>);
+    let sweep = &props.sweep;
+    let best = sweep.best();
+
+    let onmetric = {
+        let metric = metric.clone();
+        Callback::from(move |e: Event| {
+            let select: HtmlSelectElement = e.target_unchecked_into();
+            if let Some(&selected) = SweepMetric::ALL.get(select.selected_index() as usize) {
+                metric.set(selected);
+            }
+        })
+    };
+
+    let onexport = {
+        let csv = sweep.render(MatrixFormat::Csv);
+        let export_error = export_error.clone();
+        Callback::from(move |_: MouseEvent| {
+            if let Err(e) = download_text("threshold_sweep.csv", &csv) {
+                export_error.set(Some(format!("Error exporting the sweep: {:?}", e)));
+            }
+        })
+    };
+
+    let cell_style = |value: Option<f64>, is_best: bool| {
+        let background = value.map_or("#eeeeee".to_string(), |value| {
+            format!("hsl({:.0}, 70%, 75%)", value * 120.0)
+        });
+        let border = if is_best {
+            "2px solid black"
+        } else {
+            "1px solid #ffffff"
+        };
+        format!(
+            "background-color: {}; border: {}; padding: 4px 6px; text-align: center;",
+            background, border
+        )
+    };
+
+    html! {
+        <div class="sweep-results" style="margin-top: 10px;">
+            {if let Some(best) = best {
+                html! {
+                    <p>{format!(
+                        "Best setting: temporal threshold {:.2}, existential threshold {:.2} (accuracy {})",
+                        best.temporal_threshold,
+                        best.existential_threshold,
+                        format_metric(SweepMetric::Accuracy.value(best))
+                    )}</p>
+                }
+            } else {
+                html! {}
+            }}
+            <select onchange={onmetric}>
+                {for SweepMetric::ALL.iter().map(|&m| html! {
+                    <option selected={m == *metric}>{m.to_string()}</option>
+                })}
+            </select>
+            <button onclick={onexport} style="margin-left: 10px;">{"Download CSV"}</button>
+            {if let Some(error) = &*export_error {
+                html! { <p style="color: #c0392b;">{error}</p> }
+            } else {
+                html! {}
+            }}
+            <table style="border-collapse: collapse; margin-top: 10px;">
+                <thead>
+                    <tr>
+                        <th title="rows: temporal threshold, columns: existential threshold">{"T \\ E"}</th>
+                        {for sweep.existential_thresholds.iter().map(|threshold| html! {
+                            <th>{format!("{:.2}", threshold)}</th>
+                        })}
+                    </tr>
+                </thead>
+                <tbody>
+                    {for sweep.temporal_thresholds.iter().enumerate().map(|(t, temporal_threshold)| html! {
+                        <tr>
+                            <th>{format!("{:.2}", temporal_threshold)}</th>
+                            {for (0..sweep.existential_thresholds.len()).filter_map(|e| sweep.get(t, e)).map(|point| {
+                                let value = metric.value(point);
+                                let is_best = best.is_some_and(|best| std::ptr::eq(best, point));
+                                html! {
+                                    <td style={cell_style(value, is_best)}>{format_metric(value)}</td>
+                                }
+                            })}
+                        </tr>
+                    })}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+/// Offers `content` for download as a text file named `filename`.
+fn download_text(filename: &str, content: &str) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("No document object"))?;
+
+    let blob = web_sys::Blob::new_with_str_sequence_and_options(
+        &js_sys::Array::of1(&JsValue::from_str(content)),
+        web_sys::BlobPropertyBag::new().type_("text/plain"),
+    )?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+
+    let anchor: HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    anchor.click();
+
+    web_sys::Url::revoke_object_url(&url)
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PairResultsProps {
+    pub report: EvaluationReport,
+}
+
+const MATCH_COLOR: &str = "#d4edda";
+const MISMATCH_COLOR: &str = "#f8d7da";
+
+/// The texts of the cells of a pair in the `PairResults` table, with the support and confidence
+/// of the discovered halves as their tooltips.
+#[derive(Debug, PartialEq)]
+struct PairCells {
+    pair: String,
+    expected: String,
+    discovered: String,
+    expected_temporal: String,
+    discovered_temporal: String,
+    discovered_temporal_scores: String,
+    expected_existential: String,
+    discovered_existential: String,
+    discovered_existential_scores: String,
+}
+
+impl PairCells {
+    fn new(pair: &PairResult) -> Self {
+        let format_half = |half: Option<String>| half.unwrap_or_else(|| "-".to_string());
+        let format_scores = |scores: Option<(usize, f64)>| {
+            scores.map_or(String::new(), |(support, confidence)| {
+                format!("support {}, confidence {:.2}", support, confidence)
+            })
+        };
+        let actual = pair.actual.as_ref();
+        let actual_temporal = actual.and_then(|dep| dep.temporal_dependency.as_ref());
+        let actual_existential = actual.and_then(|dep| dep.existential_dependency.as_ref());
+
+        PairCells {
+            pair: format!("{} → {}", pair.expected.from, pair.expected.to),
+            expected: pair.expected.to_string(),
+            discovered: actual.map_or("not in log".to_string(), |dep| dep.to_string()),
+            expected_temporal: format_half(
+                pair.expected
+                    .temporal_dependency
+                    .as_ref()
+                    .map(|dep| dep.to_string()),
+            ),
+            discovered_temporal: format_half(actual_temporal.map(|dep| dep.to_string())),
+            discovered_temporal_scores: format_scores(
+                actual_temporal.map(|dep| (dep.support, dep.confidence)),
+            ),
+            expected_existential: format_half(
+                pair.expected
+                    .existential_dependency
+                    .as_ref()
+                    .map(|dep| dep.to_string()),
+            ),
+            discovered_existential: format_half(actual_existential.map(|dep| dep.to_string())),
+            discovered_existential_scores: format_scores(
+                actual_existential.map(|dep| (dep.support, dep.confidence)),
+            ),
+        }
+    }
+}
+
+/// Lists every ground truth pair with the expected and the discovered dependency, with the
+/// temporal and existential halves highlighted depending on whether they match.
+#[function_component(PairResults)]
+pub fn pair_results(props: &PairResultsProps) -> Html {
+    let only_mismatches = use_state(|| false);
+
+    let ontoggle = {
+        let only_mismatches = only_mismatches.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            only_mismatches.set(input.checked());
+        })
+    };
+
+    let mismatches = props
+        .report
+        .pairs
+        .iter()
+        .filter(|pair| !pair.is_match())
+        .count();
+    let cell_style = |matched: bool| {
+        format!(
+            "background-color: {}; padding: 2px 8px;",
+            if matched { MATCH_COLOR } else { MISMATCH_COLOR }
+        )
+    };
+
+    html! {
+        <div class="pair-results">
+            <h4>{format!("Pairs ({} mismatched)", mismatches)}</h4>
+            <label>
+                <input type="checkbox" checked={*only_mismatches} onchange={ontoggle} />
+                {" Only show mismatches"}
+            </label>
+            <table style="border-collapse: collapse; margin-top: 10px;">
+                <thead>
+                    <tr>
+                        <th>{"Pair"}</th>
+                        <th>{"Expected"}</th>
+                        <th>{"Discovered"}</th>
+                        <th>{"Expected Temporal"}</th>
+                        <th>{"Discovered Temporal"}</th>
+                        <th>{"Expected Existential"}</th>
+                        <th>{"Discovered Existential"}</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {for props.report.pairs.iter().filter(|pair| !*only_mismatches || !pair.is_match()).map(|pair| {
+                        let cells = PairCells::new(pair);
+                        html! {
+                            <tr>
+                                <td>{cells.pair}</td>
+                                <td>{cells.expected}</td>
+                                <td>{cells.discovered}</td>
+                                <td style={cell_style(pair.temporal_match)}>
+                                    {cells.expected_temporal}
+                                </td>
+                                <td
+                                    style={cell_style(pair.temporal_match)}
+                                    title={cells.discovered_temporal_scores}
+                                >
+                                    {cells.discovered_temporal}
+                                </td>
+                                <td style={cell_style(pair.existential_match)}>
+                                    {cells.expected_existential}
+                                </td>
+                                <td
+                                    style={cell_style(pair.existential_match)}
+                                    title={cells.discovered_existential_scores}
+                                >
+                                    {cells.discovered_existential}
+                                </td>
+                            </tr>
+                        }
+                    })}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+/// Parses the XES log `event_log_content` and compares the ground truth `deps` with its
+/// matrix, see `evaluate_traces`.
+pub fn evaluate_deps(
+    deps: &[Dependency],
+    event_log_content: &str,
+    existential_threshold: f64,
+    temporal_threshold: f64,
+    direct_threshold: f64,
+) -> Result<(usize, usize, usize, usize), ParseError> {
+    let traces: Vec<Vec<String>> = XesLog::from_bytes(event_log_content.as_bytes())?.traces()?;
+
+    Ok(evaluate_traces(
+        deps,
+        traces,
+        existential_threshold,
+        temporal_threshold,
+        direct_threshold,
+    ))
+}
+
+/// Compares the ground truth `deps` with the matrix discovered from `traces` and returns
+/// `(correct_temporal, total_temporal, correct_existential, total_existential)`.
+pub fn evaluate_traces(
+    deps: &[Dependency],
+    traces: Vec<Vec<String>>,
+    existential_threshold: f64,
+    temporal_threshold: f64,
+    direct_threshold: f64,
+) -> (usize, usize, usize, usize) {
+    let report = evaluation_report(
+        deps,
+        traces,
+        existential_threshold,
+        temporal_threshold,
+        direct_threshold,
+    );
+
+    (
+        report.correct_temporal,
+        report.total,
+        report.correct_existential,
+        report.total,
+    )
+}
+
+/// Compares the ground truth `deps` with the matrix discovered from `traces`, see [`EvaluationReport`].
+pub fn evaluation_report(
+    deps: &[Dependency],
+    traces: Vec<Vec<String>>,
+    existential_threshold: f64,
+    temporal_threshold: f64,
+    direct_threshold: f64,
+) -> EvaluationReport {
+    let matrix = generate_adj_matrix_from_traces(
+        traces,
+        existential_threshold,
+        temporal_threshold,
+        direct_threshold,
+    );
+    EvaluationReport::new(deps, &matrix)
+}
+
+/// Extracts the trailing number of an event log name, e.g. `3` from `event_log_noise_03`.
+pub fn log_number(log_name: &str) -> Option<usize> {
+    let digits = log_name.len()
+        - log_name
+            .trim_end_matches(|c: char| c.is_ascii_digit())
+            .len();
+    log_name[log_name.len() - digits..].parse().ok()
+}
+
+/// Accuracy of the dependencies discovered from one event log, measured against its ground truth.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LogAccuracy {
+    pub log: String,
+    pub report: EvaluationReport,
+}
+
+impl LogAccuracy {
+    pub fn evaluate(
+        log: &str,
+        deps: &[Dependency],
+        traces: Vec<Vec<String>>,
+        existential_threshold: f64,
+        temporal_threshold: f64,
+        direct_threshold: f64,
+    ) -> Self {
+        Self {
+            log: log.to_string(),
+            report: evaluation_report(
+                deps,
+                traces,
+                existential_threshold,
+                temporal_threshold,
+                direct_threshold,
+            ),
+        }
+    }
+
+    /// Formats the temporal accuracy, e.g. `9/10 (90%)`.
+    pub fn temporal(&self) -> String {
+        format_ratio(self.report.correct_temporal, self.report.total)
+    }
+
+    /// Formats the existential accuracy, e.g. `9/10 (90%)`.
+    pub fn existential(&self) -> String {
+        format_ratio(self.report.correct_existential, self.report.total)
+    }
+}
+
+fn format_ratio(correct: usize, total: usize) -> String {
+    let percentage = if total == 0 {
+        0
+    } else {
+        (correct as f64 / total as f64 * 100.0).round() as u32
+    };
+    format!("{}/{} ({}%)", correct, total, percentage)
+}
+
+/// The evaluated logs of one directory, e.g. the clean or the noisy synthetic logs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EvaluationSet {
+    pub name: String,
+    pub logs: Vec<LogAccuracy>,
+}
+
+impl EvaluationSet {
+    /// Sums up the reports of all logs in the set.
+    pub fn total(&self) -> LogAccuracy {
+        self.logs.iter().fold(
+            LogAccuracy {
+                log: "Total".to_string(),
+                ..Default::default()
+            },
+            |mut total, log| {
+                total.report.merge(&log.report);
+                total
+            },
+        )
+    }
+}
+
+/// Renders the per-log and aggregate accuracy of every set as a single table.
+pub fn render_accuracy_table(sets: &[EvaluationSet], format: MatrixFormat) -> String {
+    let header = ["Set", "Log", "Temporal", "Existential"].map(String::from);
+    let rows: Vec<Vec<String>> = sets
+        .iter()
+        .flat_map(|set| {
+            set.logs
+                .iter()
+                .cloned()
+                .chain(std::iter::once(set.total()))
+                .map(|log| {
+                    let (temporal, existential) = (log.temporal(), log.existential());
+                    vec![set.name.clone(), log.log, temporal, existential]
+                })
+        })
+        .collect();
+
+    render_table(&header, &rows, 2, format)
+}
+
+/// Renders a table in the given format. In the text format the first `left_aligned` columns are
+/// aligned to the left and all others to the right.
+pub(crate) fn render_table(
+    header: &[String],
+    rows: &[Vec<String>],
+    left_aligned: usize,
+    format: MatrixFormat,
+) -> String {
+    match format {
+        MatrixFormat::Text => {
+            let widths: Vec<usize> = (0..header.len())
+                .map(|i| {
+                    rows.iter()
+                        .map(Vec::as_slice)
+                        .chain(std::iter::once(header))
+                        .map(|row| row[i].chars().count())
+                        .max()
+                        .unwrap_or(0)
+                })
+                .collect();
+            let format_row = |row: &[String]| {
+                let fields: Vec<String> = row
+                    .iter()
+                    .zip(&widths)
+                    .enumerate()
+                    .map(|(i, (field, &width))| {
+                        if i < left_aligned {
+                            format!("{:<width$}", field)
+                        } else {
+                            format!("{:>width$}", field)
+                        }
+                    })
+                    .collect();
+                format!("{}\n", fields.join(" | ").trim_end())
+            };
+
+            let separator: Vec<String> = widths.iter().map(|w| "-".repeat(*w)).collect();
+            let mut output = format_row(header);
+            output.push_str(&format!("{}\n", separator.join("-|-")));
+            rows.iter()
+                .for_each(|row| output.push_str(&format_row(row)));
+            output
+        }
+        MatrixFormat::Csv => std::iter::once(header)
+            .chain(rows.iter().map(Vec::as_slice))
+            .map(|row| csv_row(row.iter().cloned()))
+            .collect(),
+        MatrixFormat::Markdown => {
+            let mut output = markdown_row(header.iter().cloned());
+            output.push_str(&markdown_row(std::iter::repeat_n(
+                "---".to_string(),
+                header.len(),
+            )));
+            rows.iter()
+                .for_each(|row| output.push_str(&markdown_row(row.iter().cloned())));
+            output
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use rstest::rstest;
+
+    use crate::dependency_types::{dependency::Dependency, existential};
+    use crate::generate_adj_matrix_from_traces;
+    use crate::ground_truth::load_ground_truth;
+    use crate::parser::XesLog;
+
+    use super::*;
+
+    fn test_dependencies(deps: &[Dependency], event_log_path: &str) {
+        let traces: Vec<Vec<String>> = XesLog::from_path(event_log_path)
+            .and_then(|log| log.traces())
+            .unwrap();
+
+        let temporal_threshold = 1.0;
+        let existential_threshold = 1.0;
+
+        let direct_threshold = 1.0;
+
+        let matrix = generate_adj_matrix_from_traces(
+            traces,
+            existential_threshold,
+            temporal_threshold,
+            direct_threshold,
+        );
+
+        // the ground truth lists every pair of distinct activities once, in matrix order
+        let adj_matrix_sorted: Vec<&Dependency> = matrix
+            .rows()
+            .enumerate()
+            .flat_map(|(i, (_, row))| &row[i + 1..])
+            .collect();
+
+        assert_eq!(deps.len(), adj_matrix_sorted.len());
+
+        for (i, dep) in deps.iter().enumerate() {
+            assert_eq!(dep.from, adj_matrix_sorted[i].from);
+            assert_eq!(dep.to, adj_matrix_sorted[i].to);
+            assert_eq!(
+                dep.temporal_dependency,
+                adj_matrix_sorted[i].temporal_dependency
+            );
+
+            if let Some(existential_dependency) = dep.existential_dependency.as_ref() {
+                if existential_dependency.dependency_type
+                    == existential::DependencyType::Equivalence
+                    || existential_dependency.dependency_type
+                        == existential::DependencyType::NegatedEquivalence
+                {
+                    assert_eq!(
+                        dep.existential_dependency.as_ref().unwrap().dependency_type,
+                        adj_matrix_sorted[i]
+                            .existential_dependency
+                            .as_ref()
+                            .unwrap()
+                            .dependency_type
+                    );
+                } else {
+                    assert_eq!(
+                        dep.existential_dependency,
+                        adj_matrix_sorted[i].existential_dependency
+                    );
+                }
+            } else {
+                assert_eq!(
+                    None, adj_matrix_sorted[i].existential_dependency,
+                    "{} and {} are existentially independent",
+                    dep.from, dep.to
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_evaluate_deps() {
+        let log = std::fs::read_to_string("./sample-data/exercise2.xes").unwrap();
+        let deps = ["A,C:d,f i,f".parse::<Dependency>().unwrap()];
+        let (_, total_temporal, _, total_existential) =
+            evaluate_deps(&deps, &log, 1.0, 1.0, 1.0).unwrap();
+        assert_eq!((total_temporal, total_existential), (1, 1));
+
+        assert!(evaluate_deps(&deps, "<log>", 1.0, 1.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_log_number() {
+        assert_eq!(log_number("event_log_03"), Some(3));
+        assert_eq!(log_number("event_log_noise_11"), Some(11));
+        assert_eq!(log_number("event_log"), None);
+    }
+
+    #[test]
+    fn test_pair_cells_implication_in_both_directions() {
+        let traces = vec![vec!["a".to_string(), "b".to_string()]];
+        let matrix = generate_adj_matrix_from_traces(traces, 1.0, 1.0, 1.0);
+        // the existential direction is left out, so the implication holds in both directions
+        let deps = ["a,b:d,f i".parse::<Dependency>().unwrap()];
+        let report = EvaluationReport::new(&deps, &matrix);
+
+        let cells = PairCells::new(&report.pairs[0]);
+        assert_eq!(cells.pair, "a → b");
+        assert_eq!(cells.expected, "≺d,⇔");
+        assert_eq!(cells.expected_existential, "⇔");
+        assert_eq!(cells.discovered, "≺d,⇔");
+        assert_eq!(
+            cells.discovered_temporal_scores,
+            "support 1, confidence 1.00"
+        );
+    }
+
+    #[test]
+    fn test_render_accuracy_table() {
+        let log = |log: &str, correct_temporal, correct_existential, total| LogAccuracy {
+            log: log.to_string(),
+            report: EvaluationReport {
+                total,
+                correct_temporal,
+                correct_existential,
+                ..Default::default()
+            },
+        };
+        let set = EvaluationSet {
+            name: "clean".to_string(),
+            logs: vec![log("L01", 10, 9, 10), log("L02", 1, 3, 3)],
+        };
+
+        assert_eq!(
+            render_accuracy_table(std::slice::from_ref(&set), MatrixFormat::Markdown),
+            "| Set | Log | Temporal | Existential |
+| --- | --- | --- | --- |
+| clean | L01 | 10/10 (100%) | 9/10 (90%) |
+| clean | L02 | 1/3 (33%) | 3/3 (100%) |
+| clean | Total | 11/13 (85%) | 12/13 (92%) |
+"
+        );
+        assert_eq!(
+            render_accuracy_table(&[set], MatrixFormat::Text),
+            "Set   | Log   |     Temporal | Existential
+------|-------|--------------|------------
+clean | L01   | 10/10 (100%) |  9/10 (90%)
+clean | L02   |    1/3 (33%) |  3/3 (100%)
+clean | Total |  11/13 (85%) | 12/13 (92%)
+"
+        );
+    }
+
+    #[rstest]
+    #[case("deps_01.txt", "./sample-data/synthetic-log/event_log_01.xes")]
+    #[case("deps_02.txt", "./sample-data/synthetic-log/event_log_02.xes")]
+    #[case("deps_03.txt", "./sample-data/synthetic-log/event_log_03.xes")]
+    #[case("deps_04.txt", "./sample-data/synthetic-log/event_log_04.xes")]
+    #[case("deps_05.txt", "./sample-data/synthetic-log/event_log_05.xes")]
+    #[case("deps_06.txt", "./sample-data/synthetic-log/event_log_06.xes")]
+    #[case("deps_07.txt", "./sample-data/synthetic-log/event_log_07.xes")]
+    #[case("deps_08.txt", "./sample-data/synthetic-log/event_log_08.xes")]
+    #[case("deps_09.txt", "./sample-data/synthetic-log/event_log_09.xes")]
+    #[case("deps_10.txt", "./sample-data/synthetic-log/event_log_10.xes")]
+    #[case("deps_11.txt", "./sample-data/synthetic-log/event_log_11.xes")]
+    fn test_dependencies_general(#[case] ground_truth: &str, #[case] event_log_path: &str) {
+        let deps = load_ground_truth(format!("./sample-data/ground-truth/{ground_truth}")).unwrap();
+        test_dependencies(&deps, event_log_path);
+    }
+}
@@ -0,0 +1,540 @@
+use super::render_table;
+use crate::{
+    dependency_types::{
+        dependency::Dependency,
+        existential::{self, ExistentialDependency},
+        temporal::{self, TemporalDependency},
+    },
+    matrix::{AdjacencyMatrix, MatrixFormat},
+};
+
+/// A class a discovered or expected dependency can be assigned to in a `ConfusionMatrix`.
+pub trait Class: Copy + Eq + std::fmt::Display + 'static {
+    /// Every class, in the order of the rows and columns of the confusion matrix
+    const ALL: &'static [Self];
+
+    fn index(self) -> usize {
+        Self::ALL
+            .iter()
+            .position(|&class| class == self)
+            .expect("class is listed in `ALL`")
+    }
+}
+
+/// Temporal dependency classes, i.e. type and direction of a `TemporalDependency`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TemporalClass {
+    DirectForward,
+    DirectBackward,
+    EventualForward,
+    EventualBackward,
+    Independent,
+}
+
+impl Class for TemporalClass {
+    const ALL: &'static [Self] = &[
+        TemporalClass::DirectForward,
+        TemporalClass::DirectBackward,
+        TemporalClass::EventualForward,
+        TemporalClass::EventualBackward,
+        TemporalClass::Independent,
+    ];
+}
+
+impl From<Option<&TemporalDependency>> for TemporalClass {
+    fn from(dependency: Option<&TemporalDependency>) -> Self {
+        use temporal::{DependencyType::*, Direction::*};
+
+        match dependency.map(|dep| (&dep.dependency_type, &dep.direction)) {
+            Some((Direct, Forward)) => TemporalClass::DirectForward,
+            Some((Direct, Backward)) => TemporalClass::DirectBackward,
+            Some((Eventual, Forward)) => TemporalClass::EventualForward,
+            Some((Eventual, Backward)) => TemporalClass::EventualBackward,
+            None => TemporalClass::Independent,
+        }
+    }
+}
+
+impl std::fmt::Display for TemporalClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemporalClass::DirectForward => write!(f, "≺d"),
+            TemporalClass::DirectBackward => write!(f, "≻d"),
+            TemporalClass::EventualForward => write!(f, "≺"),
+            TemporalClass::EventualBackward => write!(f, "≻"),
+            TemporalClass::Independent => write!(f, "-"),
+        }
+    }
+}
+
+/// Existential dependency classes, i.e. the type of an `ExistentialDependency` regardless of its direction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ExistentialClass {
+    Implication,
+    Equivalence,
+    NegatedEquivalence,
+    Nand,
+    Or,
+    Independent,
+}
+
+impl Class for ExistentialClass {
+    const ALL: &'static [Self] = &[
+        ExistentialClass::Implication,
+        ExistentialClass::Equivalence,
+        ExistentialClass::NegatedEquivalence,
+        ExistentialClass::Nand,
+        ExistentialClass::Or,
+        ExistentialClass::Independent,
+    ];
+}
+
+impl From<Option<&ExistentialDependency>> for ExistentialClass {
+    fn from(dependency: Option<&ExistentialDependency>) -> Self {
+        match dependency.map(|dep| &dep.dependency_type) {
+            Some(existential::DependencyType::Implication) => ExistentialClass::Implication,
+            Some(existential::DependencyType::Equivalence) => ExistentialClass::Equivalence,
+            Some(existential::DependencyType::NegatedEquivalence) => {
+                ExistentialClass::NegatedEquivalence
+            }
+            Some(existential::DependencyType::Nand) => ExistentialClass::Nand,
+            Some(existential::DependencyType::Or) => ExistentialClass::Or,
+            // self-relations are not evaluated and relate no pair of activities
+            Some(existential::DependencyType::Always | existential::DependencyType::Sometimes)
+            | None => ExistentialClass::Independent,
+        }
+    }
+}
+
+impl std::fmt::Display for ExistentialClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExistentialClass::Implication => write!(f, "⇒"),
+            ExistentialClass::Equivalence => write!(f, "⇔"),
+            ExistentialClass::NegatedEquivalence => write!(f, "⇎"),
+            ExistentialClass::Nand => write!(f, "⊼"),
+            ExistentialClass::Or => write!(f, "∨"),
+            ExistentialClass::Independent => write!(f, "-"),
+        }
+    }
+}
+
+/// Counts how often each expected class (row) was discovered as each class (column).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfusionMatrix<C: Class> {
+    counts: Vec<usize>,
+    classes: std::marker::PhantomData<C>,
+}
+
+impl<C: Class> Default for ConfusionMatrix<C> {
+    fn default() -> Self {
+        Self {
+            counts: vec![0; C::ALL.len() * C::ALL.len()],
+            classes: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<C: Class> ConfusionMatrix<C> {
+    pub fn add(&mut self, expected: C, actual: C) {
+        self.counts[expected.index() * C::ALL.len() + actual.index()] += 1;
+    }
+
+    /// Adds the counts of `other` to this matrix, e.g. to aggregate several logs.
+    pub fn merge(&mut self, other: &Self) {
+        self.counts
+            .iter_mut()
+            .zip(&other.counts)
+            .for_each(|(count, other)| *count += other);
+    }
+
+    pub fn count(&self, expected: C, actual: C) -> usize {
+        self.counts[expected.index() * C::ALL.len() + actual.index()]
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    pub fn correct(&self) -> usize {
+        C::ALL.iter().map(|&class| self.count(class, class)).sum()
+    }
+
+    /// Number of pairs expected to be of `class`.
+    pub fn support(&self, class: C) -> usize {
+        C::ALL.iter().map(|&actual| self.count(class, actual)).sum()
+    }
+
+    /// Number of pairs discovered as `class`.
+    pub fn predicted(&self, class: C) -> usize {
+        C::ALL
+            .iter()
+            .map(|&expected| self.count(expected, class))
+            .sum()
+    }
+
+    /// Ratio of the pairs discovered as `class` that are expected to be of `class`,
+    /// `None` if no pair was discovered as `class`.
+    pub fn precision(&self, class: C) -> Option<f64> {
+        ratio(self.count(class, class), self.predicted(class))
+    }
+
+    /// Ratio of the pairs expected to be of `class` that were discovered as `class`,
+    /// `None` if no pair is expected to be of `class`.
+    pub fn recall(&self, class: C) -> Option<f64> {
+        ratio(self.count(class, class), self.support(class))
+    }
+
+    /// Harmonic mean of precision and recall, `None` if either is undefined.
+    pub fn f1(&self, class: C) -> Option<f64> {
+        let (precision, recall) = (self.precision(class)?, self.recall(class)?);
+        if precision + recall == 0.0 {
+            Some(0.0)
+        } else {
+            Some(2.0 * precision * recall / (precision + recall))
+        }
+    }
+
+    /// Mean F1 over all classes expected at least once, where an undefined F1 counts as 0;
+    /// `None` if no class is expected.
+    pub fn macro_f1(&self) -> Option<f64> {
+        let scores: Vec<f64> = C::ALL
+            .iter()
+            .filter(|&&class| self.support(class) > 0)
+            .map(|&class| self.f1(class).unwrap_or(0.0))
+            .collect();
+
+        (!scores.is_empty()).then(|| scores.iter().sum::<f64>() / scores.len() as f64)
+    }
+}
+
+fn ratio(numerator: usize, denominator: usize) -> Option<f64> {
+    (denominator > 0).then(|| numerator as f64 / denominator as f64)
+}
+
+/// Result of comparing the expected dependency of one ground truth pair with the discovered one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PairResult {
+    pub expected: Dependency,
+    /// The discovered dependency, `None` if an activity of the pair does not occur in the log
+    pub actual: Option<Dependency>,
+    pub temporal_match: bool,
+    pub existential_match: bool,
+}
+
+impl PairResult {
+    pub fn is_match(&self) -> bool {
+        self.temporal_match && self.existential_match
+    }
+}
+
+/// Result of comparing a ground truth with a discovered adjacency matrix.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EvaluationReport {
+    /// Number of ground truth pairs
+    pub total: usize,
+    /// Number of pairs whose temporal dependency matches the ground truth exactly
+    pub correct_temporal: usize,
+    /// Number of pairs whose existential dependency matches the ground truth; the direction is
+    /// only compared for implications
+    pub correct_existential: usize,
+    pub temporal: ConfusionMatrix<TemporalClass>,
+    pub existential: ConfusionMatrix<ExistentialClass>,
+    /// Every ground truth pair, in the order of the ground truth
+    pub pairs: Vec<PairResult>,
+}
+
+impl EvaluationReport {
+    /// Compares every pair of the ground truth `deps` with the corresponding cell of `matrix`.
+    /// Pairs whose activities do not occur in the matrix count as discovered independent, both
+    /// in the confusion matrices and in the number of correct pairs.
+    pub fn new(deps: &[Dependency], matrix: &AdjacencyMatrix) -> Self {
+        let mut report = Self {
+            total: deps.len(),
+            ..Default::default()
+        };
+
+        for dep in deps {
+            let actual_dep = matrix.get(&dep.from, &dep.to);
+            let actual_temporal = actual_dep.and_then(|d| d.temporal_dependency.as_ref());
+            let actual_existential = actual_dep.and_then(|d| d.existential_dependency.as_ref());
+
+            report.temporal.add(
+                dep.temporal_dependency.as_ref().into(),
+                actual_temporal.into(),
+            );
+            report.existential.add(
+                dep.existential_dependency.as_ref().into(),
+                actual_existential.into(),
+            );
+
+            let temporal_match = dep.temporal_dependency.as_ref() == actual_temporal;
+            let existential_match =
+                existential_matches(dep.existential_dependency.as_ref(), actual_existential);
+            report.correct_temporal += usize::from(temporal_match);
+            report.correct_existential += usize::from(existential_match);
+
+            report.pairs.push(PairResult {
+                expected: dep.clone(),
+                actual: actual_dep.cloned(),
+                temporal_match,
+                existential_match,
+            });
+        }
+
+        report
+    }
+
+    /// Adds the counts of `other` to this report, e.g. to aggregate several logs.
+    pub fn merge(&mut self, other: &Self) {
+        self.total += other.total;
+        self.correct_temporal += other.correct_temporal;
+        self.correct_existential += other.correct_existential;
+        self.temporal.merge(&other.temporal);
+        self.existential.merge(&other.existential);
+        self.pairs.extend_from_slice(&other.pairs);
+    }
+
+    /// Renders the confusion matrices and the per-class precision, recall and F1 of both
+    /// temporal and existential dependencies.
+    pub fn render(&self, format: MatrixFormat) -> String {
+        [
+            render_section(
+                "Temporal confusion matrix (rows: expected, columns: discovered)",
+                self.temporal.render_counts(format),
+                format,
+            ),
+            render_section(
+                "Temporal per class",
+                self.temporal.render_metrics(format),
+                format,
+            ),
+            render_section(
+                "Existential confusion matrix (rows: expected, columns: discovered)",
+                self.existential.render_counts(format),
+                format,
+            ),
+            render_section(
+                "Existential per class",
+                self.existential.render_metrics(format),
+                format,
+            ),
+        ]
+        .join("\n")
+    }
+}
+
+fn render_section(title: &str, table: String, format: MatrixFormat) -> String {
+    match format {
+        MatrixFormat::Text => format!("{}\n{}", title, table),
+        MatrixFormat::Csv => format!(
+            "{}\n{}",
+            crate::matrix::csv_row(std::iter::once(title.to_string())).trim_end(),
+            table
+        ),
+        MatrixFormat::Markdown => format!("#### {}\n\n{}", title, table),
+    }
+}
+
+impl<C: Class> ConfusionMatrix<C> {
+    fn render_counts(&self, format: MatrixFormat) -> String {
+        let header: Vec<String> = std::iter::once(String::new())
+            .chain(C::ALL.iter().map(C::to_string))
+            .collect();
+        let rows: Vec<Vec<String>> = C::ALL
+            .iter()
+            .map(|&expected| {
+                std::iter::once(expected.to_string())
+                    .chain(
+                        C::ALL
+                            .iter()
+                            .map(|&actual| self.count(expected, actual).to_string()),
+                    )
+                    .collect()
+            })
+            .collect();
+
+        render_table(&header, &rows, 1, format)
+    }
+
+    fn render_metrics(&self, format: MatrixFormat) -> String {
+        let format_metric =
+            |metric: Option<f64>| metric.map_or("-".to_string(), |m| format!("{:.2}", m));
+        let header = ["Class", "Precision", "Recall", "F1", "Support"].map(String::from);
+        let rows: Vec<Vec<String>> = C::ALL
+            .iter()
+            .map(|&class| {
+                vec![
+                    class.to_string(),
+                    format_metric(self.precision(class)),
+                    format_metric(self.recall(class)),
+                    format_metric(self.f1(class)),
+                    self.support(class).to_string(),
+                ]
+            })
+            .collect();
+
+        render_table(&header, &rows, 1, format)
+    }
+}
+
+/// Equivalence, negated equivalence, NAND and OR relations are symmetric, so only their type
+/// is compared; all other relations have to match exactly.
+fn existential_matches(
+    expected: Option<&ExistentialDependency>,
+    actual: Option<&ExistentialDependency>,
+) -> bool {
+    match (expected, actual) {
+        (Some(expected), Some(actual)) => match expected.dependency_type {
+            existential::DependencyType::Equivalence
+            | existential::DependencyType::NegatedEquivalence
+            | existential::DependencyType::Nand
+            | existential::DependencyType::Or => expected.dependency_type == actual.dependency_type,
+            _ => expected == actual,
+        },
+        (None, None) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::generate_adj_matrix_from_traces;
+
+    #[test]
+    fn test_confusion_matrix() {
+        let mut matrix = ConfusionMatrix::<ExistentialClass>::default();
+        matrix.add(ExistentialClass::Implication, ExistentialClass::Implication);
+        matrix.add(ExistentialClass::Implication, ExistentialClass::Implication);
+        matrix.add(ExistentialClass::Implication, ExistentialClass::Or);
+        matrix.add(ExistentialClass::Or, ExistentialClass::Implication);
+
+        assert_eq!(matrix.total(), 4);
+        assert_eq!(matrix.correct(), 2);
+        assert_eq!(matrix.support(ExistentialClass::Implication), 3);
+        assert_eq!(matrix.predicted(ExistentialClass::Implication), 3);
+        assert_eq!(
+            matrix.precision(ExistentialClass::Implication),
+            Some(2.0 / 3.0)
+        );
+        assert_eq!(matrix.recall(ExistentialClass::Or), Some(0.0));
+        assert_eq!(matrix.f1(ExistentialClass::Or), Some(0.0));
+        assert_eq!(matrix.precision(ExistentialClass::Nand), None);
+        assert_eq!(matrix.f1(ExistentialClass::Nand), None);
+        // F1 of implication is 2/3, of OR 0
+        assert_eq!(matrix.macro_f1(), Some(1.0 / 3.0));
+    }
+
+    #[test]
+    fn test_evaluation_report() {
+        let traces = vec![
+            vec!["a".to_string(), "b".to_string()],
+            vec!["a".to_string()],
+        ];
+        let matrix = generate_adj_matrix_from_traces(traces, 1.0, 1.0, 1.0);
+        let deps: Vec<Dependency> = ["a,b:d,f i,b", "a,b:e,f i,f", "a,x:-,- -"]
+            .into_iter()
+            .map(|line| Dependency::from_str(line).unwrap())
+            .collect();
+
+        let report = EvaluationReport::new(&deps, &matrix);
+
+        assert_eq!(report.total, 3);
+        // a,x is expected independent and x does not occur in the log
+        assert_eq!(report.correct_temporal, 2);
+        assert_eq!(report.correct_existential, 2);
+        assert_eq!(report.temporal.correct(), report.correct_temporal);
+        assert_eq!(
+            report
+                .temporal
+                .count(TemporalClass::EventualForward, TemporalClass::DirectForward),
+            1
+        );
+        // the direction of the implication is wrong, but its class is correct
+        assert_eq!(
+            report
+                .existential
+                .count(ExistentialClass::Implication, ExistentialClass::Implication),
+            2
+        );
+        assert_eq!(
+            report
+                .existential
+                .count(ExistentialClass::Independent, ExistentialClass::Independent),
+            1
+        );
+
+        let mismatches: Vec<(&str, bool, bool)> = report
+            .pairs
+            .iter()
+            .filter(|pair| !pair.is_match())
+            .map(|pair| {
+                (
+                    pair.expected.to.as_str(),
+                    pair.temporal_match,
+                    pair.existential_match,
+                )
+            })
+            .collect();
+        assert_eq!(mismatches, [("b", false, false)]);
+        assert_eq!(report.pairs[2].actual, None);
+    }
+
+    #[test]
+    fn test_existential_matches_symmetric() {
+        let dependency = |from, to, dependency_type, direction| {
+            ExistentialDependency::new(from, to, dependency_type, direction)
+        };
+        for dependency_type in [
+            existential::DependencyType::NegatedEquivalence,
+            existential::DependencyType::Nand,
+            existential::DependencyType::Or,
+        ] {
+            let expected = dependency(
+                "a",
+                "b",
+                dependency_type.clone(),
+                existential::Direction::Both,
+            );
+            let actual = dependency("b", "a", dependency_type, existential::Direction::Both);
+            assert!(existential_matches(Some(&expected), Some(&actual)));
+        }
+
+        let expected = dependency(
+            "a",
+            "b",
+            existential::DependencyType::Implication,
+            existential::Direction::Forward,
+        );
+        let actual = dependency(
+            "b",
+            "a",
+            existential::DependencyType::Implication,
+            existential::Direction::Forward,
+        );
+        assert!(!existential_matches(Some(&expected), Some(&actual)));
+    }
+
+    #[test]
+    fn test_render_metrics() {
+        let mut matrix = ConfusionMatrix::<TemporalClass>::default();
+        matrix.add(TemporalClass::DirectForward, TemporalClass::DirectForward);
+        matrix.add(TemporalClass::DirectForward, TemporalClass::EventualForward);
+        matrix.add(TemporalClass::Independent, TemporalClass::Independent);
+
+        assert_eq!(
+            matrix.render_metrics(MatrixFormat::Markdown),
+            "| Class | Precision | Recall | F1 | Support |
+| --- | --- | --- | --- | --- |
+| ≺d | 1.00 | 0.50 | 0.67 | 2 |
+| ≻d | - | - | - | 0 |
+| ≺ | 0.00 | - | - | 0 |
+| ≻ | - | - | - | 0 |
+| - | 1.00 | 1.00 | 1.00 | 1 |
+"
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use super::{evaluation_report, render_table, report::EvaluationReport};
+use crate::{dependency_types::dependency::Dependency, matrix::MatrixFormat};
+
+/// Evenly spaced thresholds from `min` to `max` (both inclusive), `step` apart.
+pub fn threshold_grid(min: f64, max: f64, step: f64) -> Vec<f64> {
+    assert!(step > 0.0, "Step must be positive");
+
+    let steps = ((max - min) / step + 1e-9).floor();
+    if steps < 0.0 {
+        return Vec::new();
+    }
+    (0..=steps as usize)
+        // round to avoid values like 0.7000000000000001
+        .map(|i| ((min + i as f64 * step) * 1e6).round() / 1e6)
+        .collect()
+}
+
+/// Evaluation of all logs of a sweep with one combination of thresholds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SweepPoint {
+    pub temporal_threshold: f64,
+    pub existential_threshold: f64,
+    /// Reports of all logs, merged
+    pub report: EvaluationReport,
+}
+
+/// A metric a `SweepPoint` can be compared by, e.g. in the heatmap of the evaluation page.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SweepMetric {
+    /// Ratio of correct temporal and existential dependencies combined
+    #[default]
+    Accuracy,
+    TemporalAccuracy,
+    ExistentialAccuracy,
+    TemporalMacroF1,
+    ExistentialMacroF1,
+}
+
+impl SweepMetric {
+    pub const ALL: [SweepMetric; 5] = [
+        SweepMetric::Accuracy,
+        SweepMetric::TemporalAccuracy,
+        SweepMetric::ExistentialAccuracy,
+        SweepMetric::TemporalMacroF1,
+        SweepMetric::ExistentialMacroF1,
+    ];
+
+    /// Value of the metric between 0 and 1, `None` if it is undefined.
+    pub fn value(self, point: &SweepPoint) -> Option<f64> {
+        let report = &point.report;
+        let accuracy =
+            |correct: usize, total: usize| (total > 0).then(|| correct as f64 / total as f64);
+
+        match self {
+            SweepMetric::Accuracy => accuracy(
+                report.correct_temporal + report.correct_existential,
+                2 * report.total,
+            ),
+            SweepMetric::TemporalAccuracy => accuracy(report.correct_temporal, report.total),
+            SweepMetric::ExistentialAccuracy => accuracy(report.correct_existential, report.total),
+            SweepMetric::TemporalMacroF1 => report.temporal.macro_f1(),
+            SweepMetric::ExistentialMacroF1 => report.existential.macro_f1(),
+        }
+    }
+}
+
+impl std::fmt::Display for SweepMetric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SweepMetric::Accuracy => write!(f, "Accuracy"),
+            SweepMetric::TemporalAccuracy => write!(f, "Temporal accuracy"),
+            SweepMetric::ExistentialAccuracy => write!(f, "Existential accuracy"),
+            SweepMetric::TemporalMacroF1 => write!(f, "Temporal macro F1"),
+            SweepMetric::ExistentialMacroF1 => write!(f, "Existential macro F1"),
+        }
+    }
+}
+
+/// Evaluation of a set of logs over a grid of temporal and existential thresholds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThresholdSweep {
+    pub temporal_thresholds: Vec<f64>,
+    pub existential_thresholds: Vec<f64>,
+    /// One point per combination, row-major with one row per temporal threshold
+    pub points: Vec<SweepPoint>,
+}
+
+impl ThresholdSweep {
+    /// Discovers the matrix of every log with every combination of thresholds and compares it
+    /// with the ground truth of the log. The direct threshold is the same for every combination.
+    pub fn run(
+        logs: &[(Vec<Dependency>, Vec<Vec<String>>)],
+        temporal_thresholds: &[f64],
+        existential_thresholds: &[f64],
+        direct_threshold: f64,
+    ) -> Self {
+        let points = temporal_thresholds
+            .iter()
+            .flat_map(|&temporal_threshold| {
+                existential_thresholds
+                    .iter()
+                    .map(move |&existential_threshold| {
+                        let mut report = EvaluationReport::default();
+                        for (deps, traces) in logs {
+                            report.merge(&evaluation_report(
+                                deps,
+                                traces.clone(),
+                                existential_threshold,
+                                temporal_threshold,
+                                direct_threshold,
+                            ));
+                        }
+
+                        SweepPoint {
+                            temporal_threshold,
+                            existential_threshold,
+                            report,
+                        }
+                    })
+            })
+            .collect();
+
+        Self {
+            temporal_thresholds: temporal_thresholds.to_vec(),
+            existential_thresholds: existential_thresholds.to_vec(),
+            points,
+        }
+    }
+
+    pub fn get(&self, temporal_index: usize, existential_index: usize) -> Option<&SweepPoint> {
+        if existential_index >= self.existential_thresholds.len() {
+            return None;
+        }
+        self.points
+            .get(temporal_index * self.existential_thresholds.len() + existential_index)
+    }
+
+    /// The point with the highest combined accuracy; ties are broken by the mean of the
+    /// temporal and existential macro F1, and then in favour of the highest thresholds.
+    pub fn best(&self) -> Option<&SweepPoint> {
+        let key = |point: &SweepPoint| {
+            let f1 = |metric: SweepMetric| metric.value(point).unwrap_or(0.0);
+            (
+                SweepMetric::Accuracy.value(point).unwrap_or(0.0),
+                (f1(SweepMetric::TemporalMacroF1) + f1(SweepMetric::ExistentialMacroF1)) / 2.0,
+            )
+        };
+
+        // `max_by` returns the last of equal elements, i.e. the one with the highest thresholds
+        self.points
+            .iter()
+            .max_by(|a, b| key(a).partial_cmp(&key(b)).unwrap())
+    }
+
+    /// Renders one row per combination of thresholds with all `SweepMetric`s.
+    pub fn render(&self, format: MatrixFormat) -> String {
+        let header: Vec<String> = ["Temporal threshold", "Existential threshold"]
+            .into_iter()
+            .map(String::from)
+            .chain(SweepMetric::ALL.iter().map(SweepMetric::to_string))
+            .collect();
+        let rows: Vec<Vec<String>> = self
+            .points
+            .iter()
+            .map(|point| {
+                [point.temporal_threshold, point.existential_threshold]
+                    .iter()
+                    .map(|threshold| format!("{:.2}", threshold))
+                    .chain(
+                        SweepMetric::ALL
+                            .iter()
+                            .map(|metric| format_metric(metric.value(point))),
+                    )
+                    .collect()
+            })
+            .collect();
+
+        render_table(&header, &rows, 0, format)
+    }
+}
+
+pub fn format_metric(value: Option<f64>) -> String {
+    value.map_or("-".to_string(), |value| format!("{:.3}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ground_truth::parse_ground_truth;
+
+    #[test]
+    fn test_threshold_grid() {
+        assert_eq!(
+            threshold_grid(0.5, 1.0, 0.1),
+            [0.5, 0.6, 0.7, 0.8, 0.9, 1.0]
+        );
+        assert_eq!(threshold_grid(0.9, 1.0, 0.05), [0.9, 0.95, 1.0]);
+        assert_eq!(threshold_grid(1.0, 1.0, 0.1), [1.0]);
+        assert!(threshold_grid(1.0, 0.5, 0.1).is_empty());
+    }
+
+    #[test]
+    fn test_sweep() {
+        // `b` follows `a` in 4 of 5 traces and occurs in every trace
+        let traces: Vec<Vec<String>> = [["a", "b"]; 4]
+            .into_iter()
+            .chain([["b", "a"]])
+            .map(|trace| trace.iter().map(|s| s.to_string()).collect())
+            .collect();
+        let deps = parse_ground_truth("a,b:d,f e").unwrap();
+
+        let sweep = ThresholdSweep::run(&[(deps, traces)], &[0.7, 0.8, 0.9], &[0.9, 1.0], 1.0);
+
+        assert_eq!(sweep.points.len(), 6);
+        let point = sweep.get(2, 1).unwrap();
+        assert_eq!(
+            (point.temporal_threshold, point.existential_threshold),
+            (0.9, 1.0)
+        );
+        assert_eq!(point.report.correct_temporal, 0);
+        assert_eq!(sweep.get(1, 0).unwrap().report.correct_temporal, 1);
+        assert_eq!(sweep.get(0, 2), None);
+
+        let best = sweep.best().unwrap();
+        assert_eq!(
+            (best.temporal_threshold, best.existential_threshold),
+            (0.8, 1.0)
+        );
+        assert_eq!(SweepMetric::Accuracy.value(best), Some(1.0));
+
+        let csv = sweep.render(MatrixFormat::Csv);
+        assert_eq!(csv.lines().count(), 7);
+        assert!(csv.contains("\n0.80,1.00,1.000,1.000,1.000,1.000,1.000\n"));
+    }
+}
@@ -0,0 +1,367 @@
+use std::{fs, path::Path, str::FromStr};
+
+use crate::dependency_types::{
+    dependency::{format_token, Dependency, ParseDependencyError, ParseDependencyErrorKind},
+    existential, temporal,
+};
+
+/// Error returned when a ground truth file cannot be read or contains an invalid line.
+#[derive(Debug, thiserror::Error)]
+pub enum GroundTruthError {
+    #[error("Error reading {path}: {source}")]
+    Read {
+        path: String,
+        source: std::io::Error,
+    },
+    #[error("line {line}, column {column}: {kind}{}", format_token(token))]
+    Syntax {
+        /// 1-based line number
+        line: usize,
+        /// 1-based character column within the line
+        column: usize,
+        /// The offending token, empty if a token is missing
+        token: String,
+        kind: ParseDependencyErrorKind,
+    },
+}
+
+/// Parses a ground truth, i.e. the expected dependencies of an event log.
+///
+/// Every line describes the dependency of one activity pair:
+///
+/// ```text
+/// # comment
+/// from,to:temporal type,temporal direction existential type[,existential direction]
+/// a,b:d,f i,b
+/// b,e:-,- ne
+/// ```
+///
+/// - temporal type: `d` (direct), `e` (eventual) or `-` (independent)
+/// - temporal direction: `f` (forward), `b` (backward) or `-`
+/// - existential type: `i` (implication), `e` (equivalence), `ne` (negated equivalence),
+///   `n` (NAND), `o` (OR) or `-` (independent), and for the self-relation of an activity `a`
+///   (in every trace) or `s` (in some traces)
+/// - existential direction: `f`, `b` or `-`; if omitted, both directions are assumed
+///
+/// Tokens are separated by commas, colons or whitespace, so activity names may not contain
+/// any of them. Blank lines and lines starting with `#` are ignored.
+pub fn parse_ground_truth(content: &str) -> Result<Vec<Dependency>, GroundTruthError> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .map(|(index, line)| {
+            Dependency::from_str(line).map_err(
+                |ParseDependencyError {
+                     column,
+                     token,
+                     kind,
+                 }| GroundTruthError::Syntax {
+                    line: index + 1,
+                    column,
+                    token,
+                    kind,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Parses a ground truth matrix, as exported by [`crate::matrix::AdjacencyMatrix::to_csv`] or
+/// copied from a spreadsheet (tab-separated).
+///
+/// The first row lists the activities after an empty corner cell, every further row starts with
+/// its activity followed by one cell per activity in the notation of the matrix, e.g. `≺d,=>`,
+/// `-,⇎` or `None`. Only the cells above the diagonal are read, so that every pair is listed once
+/// as in [`parse_ground_truth`].
+pub fn parse_ground_truth_matrix(content: &str) -> Result<Vec<Dependency>, GroundTruthError> {
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty());
+    let Some((_, header)) = lines.next() else {
+        return Ok(Vec::new());
+    };
+    let delimiter = if header.contains('\t') { '\t' } else { ',' };
+    let activities: Vec<String> = split_fields(header, delimiter)
+        .into_iter()
+        .skip(1)
+        .map(|(_, activity)| activity.trim().to_string())
+        .collect();
+
+    let mut deps = Vec::new();
+    for (row, (index, line)) in lines.enumerate() {
+        let syntax_error = |column, token: &str, kind| GroundTruthError::Syntax {
+            line: index + 1,
+            column,
+            token: token.to_string(),
+            kind,
+        };
+        let fields = split_fields(line, delimiter);
+        let (label_column, label) = &fields[0];
+
+        let Some(from) = activities.get(row).filter(|&from| from == label.trim()) else {
+            return Err(syntax_error(
+                *label_column,
+                label,
+                ParseDependencyErrorKind::UnexpectedToken,
+            ));
+        };
+
+        for (to_index, to) in activities.iter().enumerate().skip(row + 1) {
+            let Some((cell_column, cell)) = fields.get(to_index + 1) else {
+                return Err(syntax_error(
+                    line.chars().count() + 1,
+                    "",
+                    ParseDependencyErrorKind::Missing("matrix cell"),
+                ));
+            };
+            let dep = Dependency::from_cell(from, to, cell).map_err(|e| {
+                syntax_error(
+                    cell_column + e.column - 1
+                        + cell.chars().take_while(|c| c.is_whitespace()).count(),
+                    &e.token,
+                    e.kind,
+                )
+            })?;
+            deps.push(dep);
+        }
+    }
+
+    Ok(deps)
+}
+
+/// Parses either a ground truth matrix or the line format, depending on whether the first line
+/// starts with an empty corner cell, see [`parse_ground_truth_matrix`] and [`parse_ground_truth`].
+pub fn parse_ground_truth_or_matrix(content: &str) -> Result<Vec<Dependency>, GroundTruthError> {
+    let is_matrix = content
+        .lines()
+        .find(|line| {
+            let line = line.trim();
+            !line.is_empty() && !line.starts_with('#')
+        })
+        .is_some_and(|line| line.starts_with([',', '\t']));
+
+    if is_matrix {
+        parse_ground_truth_matrix(content)
+    } else {
+        parse_ground_truth(content)
+    }
+}
+
+/// Splits a CSV line at `delimiter`, honouring double quotes, and returns every field together
+/// with the 1-based character column its content starts at.
+fn split_fields(line: &str, delimiter: char) -> Vec<(usize, String)> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut field_column = 1;
+    let mut in_quotes = false;
+    let mut chars = line.chars().enumerate().peekable();
+
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.next_if(|&(_, next)| next == '"').is_some() {
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if field.is_empty() => {
+                in_quotes = true;
+                field_column = index + 2;
+            }
+            c if c == delimiter && !in_quotes => {
+                fields.push((field_column, std::mem::take(&mut field)));
+                field_column = index + 2;
+            }
+            c => field.push(c),
+        }
+    }
+    fields.push((field_column, field));
+
+    fields
+}
+
+/// Formats dependencies in the line format read by [`parse_ground_truth`].
+pub fn format_ground_truth(deps: &[Dependency]) -> String {
+    let mut output = String::from(
+        "# from,to:temporal type,temporal direction existential type[,existential direction]\n",
+    );
+
+    for dep in deps {
+        let temporal = match &dep.temporal_dependency {
+            Some(temporal) => format!(
+                "{},{}",
+                match temporal.dependency_type {
+                    temporal::DependencyType::Direct => "d",
+                    temporal::DependencyType::Eventual => "e",
+                },
+                match temporal.direction {
+                    temporal::Direction::Forward => "f",
+                    temporal::Direction::Backward => "b",
+                }
+            ),
+            None => "-,-".to_string(),
+        };
+        let existential = match &dep.existential_dependency {
+            Some(existential) => {
+                let dependency_type = match existential.dependency_type {
+                    existential::DependencyType::Implication => "i",
+                    existential::DependencyType::Equivalence => "e",
+                    existential::DependencyType::NegatedEquivalence => "ne",
+                    existential::DependencyType::Nand => "n",
+                    existential::DependencyType::Or => "o",
+                    existential::DependencyType::Always => "a",
+                    existential::DependencyType::Sometimes => "s",
+                };
+                match existential.direction {
+                    existential::Direction::Forward => format!("{},f", dependency_type),
+                    existential::Direction::Backward => format!("{},b", dependency_type),
+                    existential::Direction::Both => dependency_type.to_string(),
+                }
+            }
+            None => "-".to_string(),
+        };
+
+        output.push_str(&format!(
+            "{},{}:{} {}\n",
+            dep.from, dep.to, temporal, existential
+        ));
+    }
+
+    output
+}
+
+/// Reads and parses the ground truth file at `path`, see [`parse_ground_truth`].
+pub fn load_ground_truth(path: impl AsRef<Path>) -> Result<Vec<Dependency>, GroundTruthError> {
+    let path = path.as_ref();
+    let content = fs::read_to_string(path).map_err(|source| GroundTruthError::Read {
+        path: path.to_string_lossy().to_string(),
+        source,
+    })?;
+
+    parse_ground_truth(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate_adj_matrix_from_traces;
+
+    const DEPS_1: &str = "
+# comment
+a,b:d,f i,b
+
+a,c:e,f i,b
+   # indented comment
+a,d:e,f e
+a,e:-,- ne
+";
+
+    #[test]
+    fn test_parse_ground_truth() {
+        let deps = parse_ground_truth(DEPS_1).unwrap();
+        assert_eq!(deps.len(), 4);
+        assert_eq!(deps[0], Dependency::from_str("a,b:d,f i,b").unwrap(),);
+        assert_eq!(
+            deps[1]
+                .temporal_dependency
+                .as_ref()
+                .unwrap()
+                .dependency_type,
+            temporal::DependencyType::Eventual
+        );
+        assert_eq!(
+            deps[2].existential_dependency.as_ref().unwrap().direction,
+            existential::Direction::Both
+        );
+        assert_eq!(deps[3].temporal_dependency, None);
+    }
+
+    #[test]
+    fn test_parse_ground_truth_error() {
+        let err = parse_ground_truth("# comment\na,b:d,f i,b\n\na,c:e,f q,b\n").unwrap_err();
+        let GroundTruthError::Syntax {
+            line,
+            column,
+            ref token,
+            kind,
+        } = err
+        else {
+            panic!("expected a syntax error, got {err}");
+        };
+        assert_eq!((line, column, token.as_str()), (4, 9, "q"));
+        assert_eq!(kind, ParseDependencyErrorKind::InvalidExistentialType);
+        assert_eq!(
+            err.to_string(),
+            "line 4, column 9: invalid existential dependency type `q`"
+        );
+    }
+
+    #[test]
+    fn test_format_ground_truth() {
+        let deps = load_ground_truth("./sample-data/ground-truth/deps_11.txt").unwrap();
+        let formatted = format_ground_truth(&deps);
+        assert_eq!(parse_ground_truth(&formatted).unwrap(), deps);
+        assert!(formatted.contains("a,e:d,b i,f\na,f:"));
+    }
+
+    #[test]
+    fn test_parse_ground_truth_matrix() {
+        let traces = vec![
+            vec!["a".to_string(), "b".to_string(), "c".to_string()],
+            vec!["a".to_string(), "c".to_string()],
+        ];
+        let matrix = generate_adj_matrix_from_traces(traces, 1.0, 1.0, 1.0);
+
+        let deps = parse_ground_truth_or_matrix(&matrix.to_csv()).unwrap();
+        let expected: Vec<&Dependency> = matrix
+            .rows()
+            .enumerate()
+            .flat_map(|(i, (_, row))| &row[i + 1..])
+            .collect();
+        assert_eq!(deps.len(), expected.len());
+        for (dep, expected) in deps.iter().zip(expected) {
+            assert_eq!(dep.to_string(), expected.to_string());
+        }
+
+        let tab_separated = "\ta\tb\na\t-,⇔\t≺d,<=\nb\t≻d,=>\tNone\n";
+        let deps = parse_ground_truth_or_matrix(tab_separated).unwrap();
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].to_string(), "≺d,<=");
+    }
+
+    #[test]
+    fn test_parse_ground_truth_matrix_error() {
+        let err = parse_ground_truth_matrix(",a,b\na,\"-,⇔\",\"≺d,x\"\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 13: invalid existential dependency type `x`"
+        );
+
+        // the column counts the characters of leading non-ASCII whitespace, not its bytes
+        let err = parse_ground_truth_matrix("\ta\tb\na\t-,⇔\t\u{3000}≺d,x\n").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 11: invalid existential dependency type `x`"
+        );
+
+        let err = parse_ground_truth_matrix(",a,b\nb,None,None\n").unwrap_err();
+        assert_eq!(err.to_string(), "line 2, column 1: unexpected token `b`");
+    }
+
+    #[test]
+    fn test_load_ground_truth() {
+        let deps = load_ground_truth("./sample-data/ground-truth/deps_01.txt").unwrap();
+        assert_eq!(deps.len(), 10);
+
+        assert!(matches!(
+            load_ground_truth("./sample-data/ground-truth/missing.txt"),
+            Err(GroundTruthError::Read { .. })
+        ));
+    }
+}
@@ -1,17 +1,25 @@
 use chrono::{DateTime, Duration, Utc};
 use dependency_types::{
-    dependency::Dependency, existential::check_existential_dependency,
-    temporal::check_temporal_dependency,
+    dependency::Dependency,
+    existential::{check_existential_dependency_in_co_occurrences, CoOccurrences},
+    temporal::check_temporal_dependency_in_variants,
 };
-use std::collections::{HashMap, HashSet};
+use matrix::{AdjacencyMatrix, MatrixMetrics};
+use std::collections::HashSet;
+use variants::Variants;
 
 pub mod dependency_types;
 pub mod epa;
 pub mod evaluation;
 pub mod event;
+pub mod ground_truth;
+pub mod matrix;
+pub mod matrix_table;
 pub mod parser;
 pub mod routes;
 pub mod state;
+pub mod variants;
+pub mod worker;
 
 pub fn generate_xes(text: &str) -> String {
     let mut output = String::with_capacity(text.len() * 8); // Estimate capacity
@@ -84,15 +92,8 @@ pub fn generate_adj_matrix_from_traces(
     traces: Vec<Vec<String>>,
     existential_threshold: f64,
     temporal_threshold: f64,
-) -> (
-    String,
-    usize,
-    usize,
-    usize,
-    usize,
-    usize,
-    HashMap<String, usize>,
-) {
+    direct_threshold: f64,
+) -> AdjacencyMatrix {
     let activities: HashSet<String> = traces
         .iter()
         .flat_map(|trace| trace.iter().cloned())
@@ -103,6 +104,7 @@ pub fn generate_adj_matrix_from_traces(
         traces,
         existential_threshold,
         temporal_threshold,
+        direct_threshold,
     )
 }
 
@@ -111,150 +113,122 @@ pub fn generate_adj_matrix_from_activities_and_traces(
     traces: Vec<Vec<String>>,
     existential_threshold: f64,
     temporal_threshold: f64,
-) -> (
-    String,
-    usize,
-    usize,
-    usize,
-    usize,
-    usize,
-    HashMap<String, usize>,
-) {
-    const MAX_DEPENDENCY_WIDTH: usize = 15;
-
-    let mut output =
-        String::with_capacity(activities.len() * activities.len() * MAX_DEPENDENCY_WIDTH);
-    let mut metrics = MatrixMetrics::default();
-
-    // Header
-    output.push_str(&format!("{:<MAX_DEPENDENCY_WIDTH$}", " "));
-
-    let mut activities_sorted: Vec<_> = activities.iter().collect();
-    activities_sorted.sort();
-
-    for activity in &activities_sorted {
-        output.push_str(&format!("{:<MAX_DEPENDENCY_WIDTH$}", activity));
-    }
-    output.push('\n');
-
-    let format_dependency = |dep: &Dependency| {
-        format!(
-            "{:<width$}",
-            format!("{}", dep),
-            width = MAX_DEPENDENCY_WIDTH
-        )
-    };
-
+    direct_threshold: f64,
+) -> AdjacencyMatrix {
     let converted_traces: Vec<Vec<&str>> = traces
         .iter()
         .map(|v| v.iter().map(|s| s.as_str()).collect())
         .collect();
+    let matrix_rows = MatrixRows::new(
+        activities,
+        &converted_traces,
+        existential_threshold,
+        temporal_threshold,
+        direct_threshold,
+    );
+
+    // the rows are independent, and are collected in order by both paths
+    #[cfg(all(feature = "parallel", not(target_arch = "wasm32")))]
+    let rows: Vec<Vec<Dependency>> = {
+        use rayon::prelude::*;
+        matrix_rows
+            .activities()
+            .par_iter()
+            .map(|from| matrix_rows.row(from))
+            .collect()
+    };
+    #[cfg(not(all(feature = "parallel", not(target_arch = "wasm32"))))]
+    let rows: Vec<Vec<Dependency>> = matrix_rows
+        .activities()
+        .iter()
+        .map(|from| matrix_rows.row(from))
+        .collect();
 
-    for from in &activities_sorted {
-        output.push_str(&format!("{:<MAX_DEPENDENCY_WIDTH$}", from));
+    matrix_rows.into_matrix(rows)
+}
 
-        for to in &activities_sorted {
-            if to != from {
-                let temporal_dependency =
-                    check_temporal_dependency(from, to, &converted_traces, temporal_threshold);
-                let existential_dependency = check_existential_dependency(
+/// The dependencies of a matrix computed row by row, for callers that report progress or
+/// spread the rows over threads, see `generate_adj_matrix_from_activities_and_traces`.
+pub struct MatrixRows<'a> {
+    activities: Vec<String>,
+    variants: Variants<'a>,
+    co_occurrences: CoOccurrences<'a>,
+    existential_threshold: f64,
+    temporal_threshold: f64,
+    direct_threshold: f64,
+}
+
+impl<'a> MatrixRows<'a> {
+    pub fn new(
+        activities: &HashSet<String>,
+        traces: &[Vec<&'a str>],
+        existential_threshold: f64,
+        temporal_threshold: f64,
+        direct_threshold: f64,
+    ) -> Self {
+        let mut activities: Vec<String> = activities.iter().cloned().collect();
+        activities.sort();
+        // every pair is checked once per variant instead of once per trace
+        let variants = Variants::from_traces(traces);
+        let co_occurrences = CoOccurrences::new(&variants);
+        MatrixRows {
+            activities,
+            variants,
+            co_occurrences,
+            existential_threshold,
+            temporal_threshold,
+            direct_threshold,
+        }
+    }
+
+    /// The sorted activities, with one row per activity.
+    pub fn activities(&self) -> &[String] {
+        &self.activities
+    }
+
+    /// The dependencies of `from` to every activity.
+    pub fn row(&self, from: &str) -> Vec<Dependency> {
+        self.activities
+            .iter()
+            .map(|to| {
+                let temporal_dependency = check_temporal_dependency_in_variants(
                     from,
                     to,
-                    &converted_traces,
-                    existential_threshold,
+                    &self.variants,
+                    self.temporal_threshold,
+                    self.direct_threshold,
                 );
-
-                let dependency = Dependency::new(
+                let existential_dependency = check_existential_dependency_in_co_occurrences(
+                    from,
+                    to,
+                    &self.co_occurrences,
+                    self.existential_threshold,
+                );
+                Dependency::new(
                     from.to_string(),
                     to.to_string(),
-                    temporal_dependency.clone(),
-                    existential_dependency.clone(),
-                );
-
-                metrics.update(&temporal_dependency, &existential_dependency);
-
-                output.push_str(&format_dependency(&dependency));
-            } else {
-                output.push_str(&format!("{:<MAX_DEPENDENCY_WIDTH$}", "TODO"));
-            }
-        }
-        output.push('\n');
+                    temporal_dependency,
+                    existential_dependency,
+                )
+            })
+            .collect()
     }
 
-    (
-        output,
-        metrics.full_independences,
-        metrics.pure_existences,
-        metrics.eventual_equivalences,
-        metrics.direct_equivalences,
-        activities.len(),
-        metrics.relationship_counts,
-    )
-}
-
-#[derive(Default)]
-struct MatrixMetrics {
-    full_independences: usize,
-    pure_existences: usize,
-    eventual_equivalences: usize,
-    direct_equivalences: usize,
-    relationship_counts: HashMap<String, usize>,
-}
-
-impl MatrixMetrics {
-    fn update(
-        &mut self,
-        temporal_dependency: &Option<dependency_types::temporal::TemporalDependency>,
-        existential_dependency: &Option<dependency_types::existential::ExistentialDependency>,
-    ) {
-        use dependency_types::{
-            existential::DependencyType as EDType, temporal::DependencyType as TDType,
-        };
-
-        let temporal_type = match temporal_dependency {
-            Some(td) => match td.dependency_type {
-                TDType::Eventual => "eventual",
-                TDType::Direct => "direct",
-            },
-            None => {
-                self.pure_existences += 1;
-                "none"
-            }
-        };
-
-        let existential_type = match existential_dependency {
-            Some(ed) => match ed.dependency_type {
-                EDType::Equivalence => "equivalence",
-                EDType::Implication => "implication",
-                EDType::NegatedEquivalence => "negated equivalence",
-                _ => "other",
-            },
-            None => {
-                if temporal_type == "none" {
-                    self.full_independences += 1;
-                }
-                "none"
-            }
-        };
-
-        // Record relationship type
-        let relationship_type = format!("({}, {})", temporal_type, existential_type);
-        *self
-            .relationship_counts
-            .entry(relationship_type)
-            .or_insert(0) += 1;
-
-        // Check for equivalences
-        if let Some(ed) = existential_dependency {
-            if ed.dependency_type == EDType::Equivalence {
-                if let Some(td) = temporal_dependency {
-                    match td.dependency_type {
-                        TDType::Eventual => self.eventual_equivalences += 1,
-                        TDType::Direct => self.direct_equivalences += 1,
-                    }
-                }
+    /// The matrix of the `rows` of all activities, in the order of `activities`.
+    pub fn into_matrix(self, rows: Vec<Vec<Dependency>>) -> AdjacencyMatrix {
+        let mut metrics = MatrixMetrics::default();
+        let mut cells = Vec::with_capacity(self.activities.len() * self.activities.len());
+        for dependency in rows.into_iter().flatten() {
+            if dependency.to != dependency.from {
+                metrics.update(
+                    &dependency.temporal_dependency,
+                    &dependency.existential_dependency,
+                );
             }
+            cells.push(dependency);
         }
+
+        AdjacencyMatrix::new(self.activities, cells, metrics)
     }
 }
 
@@ -289,6 +263,10 @@ pub fn get_traces(text: &str) -> Vec<Vec<&str>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use dependency_types::{
+        existential::{self, ExistentialDependency},
+        temporal::{self, TemporalDependency},
+    };
 
     #[test]
     fn test_get_activities_and_traces() {
@@ -332,6 +310,212 @@ activity 3,activity 1,activity 1,activity 2,
         assert_eq!(expected_traces, traces);
     }
 
+    /// Per-trace reference of `check_temporal_dependency`, which pairs the occurrences of the two
+    /// activities by scanning every trace instead of indexing the variants of the log.
+    fn reference_temporal(
+        from: &str,
+        to: &str,
+        traces: &[Vec<&str>],
+        threshold: f64,
+        direct_threshold: f64,
+    ) -> Option<TemporalDependency> {
+        // whether each occurrence pair of every trace is direct and forward
+        let mut pairs: Vec<(bool, bool)> = Vec::new();
+        for trace in traces {
+            let positions = |activity: &str| -> Vec<usize> {
+                (0..trace.len()).filter(|&i| trace[i] == activity).collect()
+            };
+            let (froms, tos) = (positions(from), positions(to));
+            if from == to {
+                pairs.extend(froms.windows(2).map(|pair| (pair[1] == pair[0] + 1, true)));
+                continue;
+            }
+            let (Some(&last_from), Some(&last_to)) = (froms.last(), tos.last()) else {
+                continue;
+            };
+            let (mut f, mut t) = (0, 0);
+            while f < froms.len() && t < tos.len() {
+                let forward = froms[f] < tos[t];
+                pairs.push((froms[f].abs_diff(tos[t]) == 1, forward));
+                f += usize::from(forward);
+                t += 1;
+            }
+            pairs.extend(
+                froms[f..]
+                    .iter()
+                    .filter(|&&p| p < last_to)
+                    .map(|_| (false, true)),
+            );
+            pairs.extend(tos[t..].iter().map(|&p| (false, last_from < p)));
+        }
+        if pairs.is_empty() {
+            return None;
+        }
+
+        let total = pairs.len() as f64;
+        let forward = pairs.iter().filter(|(_, forward)| *forward).count() as f64;
+        let (direction, is_forward, confidence) = if forward / total >= threshold {
+            (temporal::Direction::Forward, true, forward / total)
+        } else if (total - forward) / total >= threshold {
+            (
+                temporal::Direction::Backward,
+                false,
+                (total - forward) / total,
+            )
+        } else {
+            return None;
+        };
+        let in_direction = pairs.iter().filter(|(_, forward)| *forward == is_forward);
+        let direct = in_direction.clone().filter(|(direct, _)| *direct).count() as f64;
+        let dependency_type = if direct / in_direction.count() as f64 >= direct_threshold {
+            temporal::DependencyType::Direct
+        } else {
+            temporal::DependencyType::Eventual
+        };
+        Some(
+            TemporalDependency::new(from, to, dependency_type, direction)
+                .with_scores(pairs.len(), confidence),
+        )
+    }
+
+    /// Number of `traces` a relation applies to, and the ratio of them in which it holds, given
+    /// whether a trace contains `from` and `to`.
+    fn reference_ratio(
+        from: &str,
+        to: &str,
+        traces: &[Vec<&str>],
+        applies: impl Fn(bool, bool) -> bool,
+        holds: impl Fn(bool, bool) -> bool,
+    ) -> (usize, f64) {
+        let contained: Vec<(bool, bool)> = traces
+            .iter()
+            .map(|trace| (trace.contains(&from), trace.contains(&to)))
+            .filter(|&(from, to)| applies(from, to))
+            .collect();
+        let valid = contained.iter().filter(|&&(from, to)| holds(from, to));
+        (
+            contained.len(),
+            valid.count() as f64 / contained.len() as f64,
+        )
+    }
+
+    /// Per-trace reference of `check_existential_dependency`, which checks every trace for the
+    /// activities instead of counting their co-occurrences per variant.
+    fn reference_existential(
+        from: &str,
+        to: &str,
+        traces: &[Vec<&str>],
+        threshold: f64,
+    ) -> Option<ExistentialDependency> {
+        use existential::{DependencyType::*, Direction::*};
+
+        let dependency = |(support, confidence), dependency_type, direction| {
+            Some(
+                ExistentialDependency::new(from, to, dependency_type, direction)
+                    .with_scores(support, confidence),
+            )
+        };
+        let ratio = |applies: fn(bool, bool) -> bool, holds: fn(bool, bool) -> bool| {
+            reference_ratio(from, to, traces, applies, holds)
+        };
+        let every = |_, _| true;
+
+        if from == to {
+            let occurrence = ratio(every, |from, _| from);
+            return if occurrence.1 == 0.0 {
+                None
+            } else if occurrence.1 >= threshold {
+                dependency(occurrence, Always, Both)
+            } else {
+                dependency(ratio(every, |from, _| !from), Sometimes, Both)
+            };
+        }
+
+        let implication = ratio(every, |from, to| !from || to);
+        let reverse_implication = ratio(every, |from, to| from || !to);
+        match (
+            implication.1 >= threshold,
+            reverse_implication.1 >= threshold,
+        ) {
+            (true, true) if implication.1 <= reverse_implication.1 => {
+                return dependency(implication, Equivalence, Forward)
+            }
+            (true, true) => return dependency(reverse_implication, Equivalence, Forward),
+            (true, false) => return dependency(implication, Implication, Forward),
+            (false, true) => return dependency(reverse_implication, Implication, Backward),
+            (false, false) => {}
+        }
+
+        let negated_equivalence = ratio(every, |from, to| from != to);
+        let nand = ratio(every, |from, to| !(from && to));
+        let or = ratio(every, |from, to| from || to);
+        [
+            (negated_equivalence, NegatedEquivalence, Forward),
+            (nand, Nand, Both),
+            (or, Or, Both),
+        ]
+        .into_iter()
+        .find(|((_, confidence), _, _)| *confidence >= threshold)
+        .and_then(|(ratio, dependency_type, direction)| {
+            dependency(ratio, dependency_type, direction)
+        })
+    }
+
+    #[test]
+    fn test_matrix_matches_per_trace_reference() {
+        // few variants repeated many times, with loops, optional activities and noise in both
+        // directions
+        let variants = [
+            (vec!["A", "B", "C", "D"], 40),
+            (vec!["A", "C", "B", "D"], 15),
+            (vec!["A", "B", "B", "D"], 10),
+            (vec!["A", "D", "B"], 3),
+            (vec!["E", "A", "B", "E", "D"], 2),
+            (vec!["C", "E"], 1),
+        ];
+        let repeated: Vec<Vec<String>> = variants
+            .iter()
+            .flat_map(|(trace, count)| {
+                std::iter::repeat_n(trace.iter().map(|a| a.to_string()).collect(), *count)
+            })
+            .collect();
+        let noisy =
+            parser::XesLog::from_path("./sample-data/synthetic-logs-noise/event_log_noise_05.xes")
+                .and_then(|log| log.traces())
+                .unwrap();
+
+        for traces in [repeated, noisy] {
+            let matrix = generate_adj_matrix_from_traces(traces.clone(), 0.9, 0.8, 0.7);
+            let traces: Vec<Vec<&str>> = traces
+                .iter()
+                .map(|trace| trace.iter().map(String::as_str).collect())
+                .collect();
+            let activities: HashSet<&str> = traces.iter().flatten().copied().collect();
+            assert_eq!(matrix.iter().count(), activities.len() * activities.len());
+
+            for dependency in matrix.iter() {
+                let (from, to) = (dependency.from.as_str(), dependency.to.as_str());
+                // the scores are ignored by the equality of dependencies
+                let temporal = |dependency: Option<&TemporalDependency>| {
+                    dependency.map(|d| (d.clone(), d.support, d.confidence))
+                };
+                let existential = |dependency: Option<&ExistentialDependency>| {
+                    dependency.map(|d| (d.clone(), d.support, d.confidence))
+                };
+                assert_eq!(
+                    temporal(dependency.temporal_dependency.as_ref()),
+                    temporal(reference_temporal(from, to, &traces, 0.8, 0.7).as_ref()),
+                    "temporal dependency of {from} and {to}"
+                );
+                assert_eq!(
+                    existential(dependency.existential_dependency.as_ref()),
+                    existential(reference_existential(from, to, &traces, 0.9).as_ref()),
+                    "existential dependency of {from} and {to}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_get_traces() {
         let traces = "
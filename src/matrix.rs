@@ -0,0 +1,555 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::dependency_types::{
+    dependency::Dependency,
+    existential::{DependencyType as EDType, ExistentialDependency},
+    temporal::{DependencyType as TDType, TemporalDependency},
+};
+
+/// Width of a single column in the text rendering of the matrix.
+const MAX_DEPENDENCY_WIDTH: usize = 15;
+
+/// Width of a single column in the text rendering of the matrix with confidences.
+const MAX_SCORED_DEPENDENCY_WIDTH: usize = 20;
+
+/// Adjacency matrix of the dependencies between all activities of an event log.
+///
+/// The activities are kept sorted and the cells are stored row-major, i.e. the cell in row
+/// `from` and column `to` holds the `Dependency` from `from` to `to`.
+///
+/// The diagonal holds the self-relation of each activity, which describes how it repeats
+/// within a trace:
+///
+/// | Cell   | Meaning                                                          |
+/// |--------|------------------------------------------------------------------|
+/// | `-,∀`  | always once: occurs exactly once in every trace                  |
+/// | `-,∃`  | at most once: occurs at most once per trace, but not in all      |
+/// | `≺d,…` | directly repeated: every repetition immediately follows (`A A`)  |
+/// | `≺,…`  | eventually repeated: other activities occur in between (`A B A`) |
+///
+/// For repeated activities the existential part is `∀` (`Always`) if the activity occurs in
+/// every trace and `∃` (`Sometimes`) otherwise, so that a diagonal cell is never `None`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AdjacencyMatrix {
+    activities: Vec<String>,
+    cells: Vec<Dependency>,
+    metrics: MatrixMetrics,
+}
+
+impl AdjacencyMatrix {
+    /// Creates a matrix from sorted `activities` and their row-major `cells`.
+    pub(crate) fn new(
+        activities: Vec<String>,
+        cells: Vec<Dependency>,
+        metrics: MatrixMetrics,
+    ) -> Self {
+        debug_assert!(activities.windows(2).all(|w| w[0] < w[1]));
+        debug_assert_eq!(activities.len() * activities.len(), cells.len());
+
+        Self {
+            activities,
+            cells,
+            metrics,
+        }
+    }
+
+    /// The activities of the matrix in sorted order.
+    pub fn activities(&self) -> &[String] {
+        &self.activities
+    }
+
+    /// Number of activities, i.e. the number of rows (and columns) of the matrix.
+    pub fn len(&self) -> usize {
+        self.activities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.activities.is_empty()
+    }
+
+    /// Index of `activity` in the rows and columns of the matrix.
+    pub fn index_of(&self, activity: &str) -> Option<usize> {
+        self.activities
+            .binary_search_by(|a| a.as_str().cmp(activity))
+            .ok()
+    }
+
+    /// Returns the dependency from `from` to `to`, if both activities are part of the matrix.
+    pub fn get(&self, from: &str, to: &str) -> Option<&Dependency> {
+        let row = self.index_of(from)?;
+        let column = self.index_of(to)?;
+        self.cells.get(row * self.len() + column)
+    }
+
+    /// Returns all dependencies starting at `from`, ordered by their target activity.
+    pub fn row(&self, from: &str) -> Option<&[Dependency]> {
+        let row = self.index_of(from)?;
+        let n = self.len();
+        Some(&self.cells[row * n..(row + 1) * n])
+    }
+
+    /// Returns all dependencies ending at `to`, ordered by their source activity.
+    pub fn column(&self, to: &str) -> Option<impl Iterator<Item = &Dependency>> {
+        let column = self.index_of(to)?;
+        Some(self.cells.iter().skip(column).step_by(self.len()))
+    }
+
+    /// Iterates over the rows of the matrix together with their activity.
+    pub fn rows(&self) -> impl Iterator<Item = (&str, &[Dependency])> {
+        self.activities
+            .iter()
+            .map(String::as_str)
+            .zip(self.cells.chunks(self.len().max(1)))
+    }
+
+    /// Iterates over all cells of the matrix in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = &Dependency> {
+        self.cells.iter()
+    }
+
+    pub fn metrics(&self) -> &MatrixMetrics {
+        &self.metrics
+    }
+
+    /// Renders the matrix in the given output format.
+    pub fn render(&self, format: MatrixFormat) -> String {
+        match format {
+            MatrixFormat::Text => self.to_string(),
+            MatrixFormat::Csv => self.to_csv(),
+            MatrixFormat::Markdown => self.to_markdown(),
+        }
+    }
+
+    /// Renders the matrix in the given output format, following every dependency with the
+    /// confidence of its temporal and existential part, e.g. `≺d,=> (0.95,1.00)`.
+    ///
+    /// The support and confidence of a cell are available through its `Dependency`.
+    pub fn render_with_scores(&self, format: MatrixFormat) -> String {
+        match format {
+            MatrixFormat::Text => format!("{:#}", self),
+            MatrixFormat::Csv => self.csv(true),
+            MatrixFormat::Markdown => self.markdown(true),
+        }
+    }
+
+    /// Renders the matrix as CSV, with the activities as header row and header column.
+    pub fn to_csv(&self) -> String {
+        self.csv(false)
+    }
+
+    /// Renders the matrix as a Markdown table.
+    pub fn to_markdown(&self) -> String {
+        self.markdown(false)
+    }
+
+    fn csv(&self, scores: bool) -> String {
+        let mut output = String::new();
+
+        output.push_str(&csv_row(
+            std::iter::once(String::new()).chain(self.activities.iter().cloned()),
+        ));
+        for (from, row) in self.rows() {
+            output.push_str(&csv_row(
+                std::iter::once(from.to_string()).chain(row.iter().map(|d| cell(d, scores))),
+            ));
+        }
+
+        output
+    }
+
+    fn markdown(&self, scores: bool) -> String {
+        let mut output = String::new();
+
+        output.push_str(&markdown_row(
+            std::iter::once(String::new()).chain(self.activities.iter().cloned()),
+        ));
+        output.push_str(&markdown_row(std::iter::repeat_n(
+            "---".to_string(),
+            self.len() + 1,
+        )));
+        for (from, row) in self.rows() {
+            output.push_str(&markdown_row(
+                std::iter::once(from.to_string()).chain(row.iter().map(|d| cell(d, scores))),
+            ));
+        }
+
+        output
+    }
+}
+
+fn cell(dependency: &Dependency, scores: bool) -> String {
+    if scores {
+        format!("{:#}", dependency)
+    } else {
+        dependency.to_string()
+    }
+}
+
+/// Output formats an `AdjacencyMatrix` can be rendered in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum MatrixFormat {
+    /// Fixed-width text, as shown in the web interface
+    #[default]
+    Text,
+    /// Comma-separated values
+    Csv,
+    /// Markdown table
+    Markdown,
+}
+
+pub(crate) fn csv_row(fields: impl Iterator<Item = String>) -> String {
+    let fields: Vec<String> = fields
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field
+            }
+        })
+        .collect();
+    format!("{}\n", fields.join(","))
+}
+
+pub(crate) fn markdown_row(fields: impl Iterator<Item = String>) -> String {
+    let fields: Vec<String> = fields.map(|field| field.replace('|', "\\|")).collect();
+    format!("| {} |\n", fields.join(" | "))
+}
+
+impl std::fmt::Display for AdjacencyMatrix {
+    /// Renders the matrix as fixed-width text, with one column per activity.
+    ///
+    /// Activity names and dependencies that are longer than the column width are not
+    /// truncated and will shift the remaining columns of their row.
+    ///
+    /// The alternate form (`{:#}`) uses wider columns and shows the confidences of every
+    /// dependency.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scores = f.alternate();
+        let width = if scores {
+            MAX_SCORED_DEPENDENCY_WIDTH
+        } else {
+            MAX_DEPENDENCY_WIDTH
+        };
+
+        write!(f, "{:<width$}", " ")?;
+        for activity in &self.activities {
+            write!(f, "{:<width$}", activity)?;
+        }
+        writeln!(f)?;
+
+        for (from, row) in self.rows() {
+            write!(f, "{:<width$}", from)?;
+            for dependency in row {
+                write!(f, "{:<width$}", cell(dependency, scores))?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Summary of the kinds of dependencies found between distinct activities of a matrix.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MatrixMetrics {
+    /// Pairs without any temporal or existential dependency.
+    pub full_independences: usize,
+    /// Pairs without a temporal dependency.
+    pub pure_existences: usize,
+    /// Pairs with an eventual temporal dependency and an equivalence.
+    pub eventual_equivalences: usize,
+    /// Pairs with a direct temporal dependency and an equivalence.
+    pub direct_equivalences: usize,
+    /// Number of pairs per `(temporal, existential)` relationship type.
+    pub relationship_counts: HashMap<String, usize>,
+}
+
+impl MatrixMetrics {
+    pub(crate) fn update(
+        &mut self,
+        temporal_dependency: &Option<TemporalDependency>,
+        existential_dependency: &Option<ExistentialDependency>,
+    ) {
+        let temporal_type = match temporal_dependency {
+            Some(td) => match td.dependency_type {
+                TDType::Eventual => "eventual",
+                TDType::Direct => "direct",
+            },
+            None => {
+                self.pure_existences += 1;
+                "none"
+            }
+        };
+
+        let existential_type = match existential_dependency {
+            Some(ed) => match ed.dependency_type {
+                EDType::Equivalence => "equivalence",
+                EDType::Implication => "implication",
+                EDType::NegatedEquivalence => "negated equivalence",
+                EDType::Nand => "nand",
+                EDType::Or => "or",
+                EDType::Always => "always",
+                EDType::Sometimes => "sometimes",
+            },
+            None => {
+                if temporal_type == "none" {
+                    self.full_independences += 1;
+                }
+                "none"
+            }
+        };
+
+        // Record relationship type
+        let relationship_type = format!("({}, {})", temporal_type, existential_type);
+        *self
+            .relationship_counts
+            .entry(relationship_type)
+            .or_insert(0) += 1;
+
+        // Check for equivalences
+        if let Some(ed) = existential_dependency {
+            if ed.dependency_type == EDType::Equivalence {
+                if let Some(td) = temporal_dependency {
+                    match td.dependency_type {
+                        TDType::Eventual => self.eventual_equivalences += 1,
+                        TDType::Direct => self.direct_equivalences += 1,
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        dependency_types::{existential, temporal},
+        generate_adj_matrix_from_traces,
+        parser::XesLog,
+    };
+
+    use super::*;
+
+    fn traces(traces: &[&[&str]]) -> Vec<Vec<String>> {
+        traces
+            .iter()
+            .map(|trace| trace.iter().map(|a| a.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_cell_lookup() {
+        let matrix = generate_adj_matrix_from_traces(
+            traces(&[&["B", "A", "C"], &["B", "C"]]),
+            1.0,
+            1.0,
+            1.0,
+        );
+
+        assert_eq!(matrix.activities(), ["A", "B", "C"]);
+        assert_eq!(matrix.len(), 3);
+        assert_eq!(matrix.index_of("C"), Some(2));
+        assert_eq!(matrix.index_of("D"), None);
+        assert!(matrix.get("A", "D").is_none());
+
+        let dependency = matrix.get("B", "A").unwrap();
+        assert_eq!(dependency.from, "B");
+        assert_eq!(dependency.to, "A");
+        assert_eq!(
+            dependency.temporal_dependency,
+            Some(TemporalDependency::new(
+                "B",
+                "A",
+                temporal::DependencyType::Direct,
+                temporal::Direction::Forward
+            ))
+        );
+        assert_eq!(
+            dependency.existential_dependency,
+            Some(ExistentialDependency::new(
+                "B",
+                "A",
+                existential::DependencyType::Implication,
+                existential::Direction::Backward
+            ))
+        );
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let matrix = generate_adj_matrix_from_traces(
+            traces(&[&["A", "B", "C"], &["A", "C"]]),
+            1.0,
+            1.0,
+            1.0,
+        );
+
+        let row: Vec<_> = matrix
+            .row("B")
+            .unwrap()
+            .iter()
+            .map(|d| d.to.as_str())
+            .collect();
+        assert_eq!(row, ["A", "B", "C"]);
+        assert!(matrix.row("B").unwrap().iter().all(|d| d.from == "B"));
+
+        let column: Vec<_> = matrix
+            .column("B")
+            .unwrap()
+            .map(|d| d.from.as_str())
+            .collect();
+        assert_eq!(column, ["A", "B", "C"]);
+        assert!(matrix.column("B").unwrap().all(|d| d.to == "B"));
+
+        let rows: Vec<_> = matrix.rows().map(|(activity, _)| activity).collect();
+        assert_eq!(rows, ["A", "B", "C"]);
+        assert_eq!(matrix.iter().count(), 9);
+    }
+
+    #[test]
+    fn test_metrics() {
+        let matrix = generate_adj_matrix_from_traces(
+            traces(&[&["A", "B", "C"], &["A", "C", "B"], &["D"]]),
+            1.0,
+            1.0,
+            1.0,
+        );
+        let metrics = matrix.metrics();
+
+        // B and C are concurrent, D never occurs together with A, B or C
+        assert_eq!(metrics.pure_existences, 8);
+        assert_eq!(metrics.full_independences, 0);
+        assert_eq!(metrics.eventual_equivalences, 4);
+        assert_eq!(metrics.direct_equivalences, 0);
+        assert_eq!(metrics.relationship_counts["(none, equivalence)"], 2);
+        assert_eq!(
+            metrics.relationship_counts["(none, negated equivalence)"],
+            6
+        );
+        assert_eq!(metrics.relationship_counts.values().sum::<usize>(), 12);
+    }
+
+    #[test]
+    fn test_self_relations() {
+        let matrix = generate_adj_matrix_from_traces(
+            traces(&[
+                &["A", "B", "B", "C"],
+                &["A", "B", "D"],
+                &["A", "C", "D", "C"],
+            ]),
+            1.0,
+            1.0,
+            1.0,
+        );
+
+        let self_relation = |activity: &str| matrix.get(activity, activity).unwrap().to_string();
+        assert_eq!(self_relation("A"), "-,∀");
+        assert_eq!(self_relation("B"), "≺d,∃");
+        assert_eq!(self_relation("C"), "≺,∃");
+        assert_eq!(self_relation("D"), "-,∃");
+        // an activity that occurs at most once is told apart from an independent pair
+        assert_ne!(self_relation("D"), "None");
+
+        // self-relations are not part of the metrics
+        assert_eq!(
+            matrix.metrics().relationship_counts.values().sum::<usize>(),
+            12
+        );
+    }
+
+    #[test]
+    fn test_self_relations_with_loops() {
+        let traces = XesLog::from_path("./sample-data/synthetic-log/event_log_08.xes")
+            .and_then(|log| log.traces())
+            .unwrap();
+        let matrix = generate_adj_matrix_from_traces(traces, 1.0, 1.0, 1.0);
+
+        let self_relation = |activity: &str| matrix.get(activity, activity).unwrap();
+        assert_eq!(self_relation("a").to_string(), "-,∀");
+        assert_eq!(self_relation("e").to_string(), "-,∃");
+        for activity in ["b", "c"] {
+            // both are repeated directly in some traces and eventually in others
+            assert_eq!(
+                self_relation(activity).temporal_dependency,
+                Some(TemporalDependency::new(
+                    activity,
+                    activity,
+                    temporal::DependencyType::Eventual,
+                    temporal::Direction::Forward
+                ))
+            );
+            assert_eq!(self_relation(activity).to_string(), "≺,∀");
+        }
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let matrix = generate_adj_matrix_from_traces(traces(&[&["A", "B,C"]]), 1.0, 1.0, 1.0);
+        let expected = "\
+,A,\"B,C\"
+A,\"-,∀\",\"≺d,⇔\"
+\"B,C\",\"≻d,⇔\",\"-,∀\"
+";
+        assert_eq!(expected, matrix.render(MatrixFormat::Csv));
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        let matrix =
+            generate_adj_matrix_from_traces(traces(&[&["A", "B|C"], &["A"]]), 1.0, 1.0, 1.0);
+        let expected = "\
+|  | A | B\\|C |
+| --- | --- | --- |
+| A | -,∀ | ≺d,<= |
+| B\\|C | ≻d,=> | -,∃ |
+";
+        assert_eq!(expected, matrix.render(MatrixFormat::Markdown));
+    }
+
+    #[test]
+    fn test_display() {
+        let matrix = generate_adj_matrix_from_traces(traces(&[&["A", "B"]]), 1.0, 1.0, 1.0);
+        let expected = format!(
+            "{:<15}{:<15}{:<15}\n{:<15}{:<15}{:<15}\n{:<15}{:<15}{:<15}\n",
+            " ", "A", "B", "A", "-,∀", "≺d,⇔", "B", "≻d,⇔", "-,∀"
+        );
+        assert_eq!(expected, matrix.to_string());
+    }
+
+    #[test]
+    fn test_render_with_scores() {
+        let matrix = generate_adj_matrix_from_traces(
+            traces(&[&["A", "B"], &["A", "B"], &["A", "B"], &["B", "A"], &["B"]]),
+            0.75,
+            0.75,
+            1.0,
+        );
+        let dependency = matrix.get("A", "B").unwrap();
+        let temporal = dependency.temporal_dependency.as_ref().unwrap();
+        let existential = dependency.existential_dependency.as_ref().unwrap();
+        assert_eq!((4, 0.75), (temporal.support, temporal.confidence));
+        // B => A only holds in 4 of the 5 traces
+        assert_eq!((5, 0.8), (existential.support, existential.confidence));
+
+        let expected = "\
+|  | A | B |
+| --- | --- | --- |
+| A | -,∀ (-,0.80) | ≺d,⇔ (0.75,0.80) |
+| B | ≻d,⇔ (0.75,0.80) | -,∀ (-,1.00) |
+";
+        assert_eq!(expected, matrix.render_with_scores(MatrixFormat::Markdown));
+        let text = matrix.render_with_scores(MatrixFormat::Text);
+        assert_eq!(
+            Some(
+                format!(
+                    "{:<20}{:<20}{:<20}",
+                    "B", "≻d,⇔ (0.75,0.80)", "-,∀ (-,1.00)"
+                )
+                .as_str()
+            ),
+            text.lines().nth(2)
+        );
+    }
+}
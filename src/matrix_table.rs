@@ -0,0 +1,305 @@
+use web_sys::Element;
+use yew::prelude::*;
+
+use crate::{
+    dependency_types::{dependency::Dependency, existential, temporal},
+    matrix::AdjacencyMatrix,
+};
+
+/// Widest an activity name is shown in a header before it is cut off with an ellipsis.
+const HEADER_WIDTH: &str = "160px";
+
+/// Background of the header of the hovered row and column.
+const HIGHLIGHT_HEADER_COLOR: &str = "#ffe8a1";
+
+/// Background of a cell by the type of its temporal dependency.
+fn temporal_color(dependency_type: Option<&temporal::DependencyType>) -> &'static str {
+    match dependency_type {
+        Some(temporal::DependencyType::Direct) => "#9ec5fe",
+        Some(temporal::DependencyType::Eventual) => "#cfe2ff",
+        None => "#ffffff",
+    }
+}
+
+/// Underline of a cell by the type of its existential dependency.
+fn existential_color(dependency_type: Option<&existential::DependencyType>) -> &'static str {
+    match dependency_type {
+        Some(existential::DependencyType::Implication) => "#fd7e14",
+        Some(existential::DependencyType::Equivalence) => "#198754",
+        Some(existential::DependencyType::NegatedEquivalence) => "#dc3545",
+        Some(existential::DependencyType::Nand) => "#6f42c1",
+        Some(existential::DependencyType::Or) => "#20c997",
+        Some(existential::DependencyType::Always) => "#495057",
+        Some(existential::DependencyType::Sometimes) => "#adb5bd",
+        None => "transparent",
+    }
+}
+
+/// The tooltip of a cell, with the type, direction and scores of both parts of `dependency`.
+fn dependency_details(dependency: &Dependency) -> String {
+    let temporal = match &dependency.temporal_dependency {
+        Some(dep) => format!(
+            "{} ({:?}, {:?}), support {}, confidence {:.2}",
+            dep, dep.dependency_type, dep.direction, dep.support, dep.confidence
+        ),
+        None => "none".to_string(),
+    };
+    let existential = match &dependency.existential_dependency {
+        Some(dep) => format!(
+            "{} ({:?}, {:?}), support {}, confidence {:.2}",
+            dep, dep.dependency_type, dep.direction, dep.support, dep.confidence
+        ),
+        None => "none".to_string(),
+    };
+    format!(
+        "{} → {}\nTemporal: {}\nExistential: {}",
+        dependency.from, dependency.to, temporal, existential
+    )
+}
+
+/// Index of the row and the column of the header or cell an event happened in, taken from its
+/// `data-row` and `data-column` attributes.
+fn hovered_position(target: Option<Element>) -> (Option<usize>, Option<usize>) {
+    let Some(cell) = target.and_then(|target| target.closest("td, th").ok().flatten()) else {
+        return (None, None);
+    };
+    let index = |name: &str| {
+        cell.get_attribute(name)
+            .and_then(|value| value.parse::<usize>().ok())
+    };
+    (index("data-row"), index("data-column"))
+}
+
+#[derive(Properties)]
+pub struct MatrixTableProps {
+    pub matrix: AdjacencyMatrix,
+    /// Follows every cell with the confidences of its dependency
+    #[prop_or_default]
+    pub show_scores: bool,
+}
+
+/// Besides the dependencies, compares their scores, which the equality of dependencies
+/// ignores, so that the table is rendered again when only the scores of its matrix changed.
+impl PartialEq for MatrixTableProps {
+    fn eq(&self, other: &Self) -> bool {
+        let scores = |matrix: &AdjacencyMatrix| {
+            matrix
+                .iter()
+                .map(|dependency| {
+                    (
+                        dependency
+                            .temporal_dependency
+                            .as_ref()
+                            .map(|dep| (dep.support, dep.confidence)),
+                        dependency
+                            .existential_dependency
+                            .as_ref()
+                            .map(|dep| (dep.support, dep.confidence)),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        self.show_scores == other.show_scores
+            && self.matrix == other.matrix
+            && scores(&self.matrix) == scores(&other.matrix)
+    }
+}
+
+/// Shows an adjacency matrix as a table with sticky activity headers, the cells coloured by the
+/// temporal (background) and existential (underline) type of their dependency, and the row and
+/// column of the hovered cell highlighted. Every header and cell has the full activity names
+/// and dependency details as tooltip.
+#[function_component(MatrixTable)]
+pub fn matrix_table(props: &MatrixTableProps) -> Html {
+    let hovered = use_state(|| (None::<usize>, None::<usize>));
+    let matrix = &props.matrix;
+
+    let onmouseover = {
+        let hovered = hovered.clone();
+        Callback::from(move |e: MouseEvent| {
+            let position = hovered_position(e.target_dyn_into::<Element>());
+            if *hovered != position {
+                hovered.set(position);
+            }
+        })
+    };
+    let onmouseleave = {
+        let hovered = hovered.clone();
+        Callback::from(move |_: MouseEvent| hovered.set((None, None)))
+    };
+
+    let (hovered_row, hovered_column) = *hovered;
+    let header_style = |highlighted: bool| {
+        format!(
+            "position: sticky; max-width: {}; overflow: hidden; text-overflow: ellipsis; white-space: nowrap; \
+            padding: 4px 6px; border: 1px solid #dddddd; background-color: {};",
+            HEADER_WIDTH,
+            if highlighted { HIGHLIGHT_HEADER_COLOR } else { "#f0f0f0" }
+        )
+    };
+    let cell_style = |dependency: &Dependency, highlighted: bool| {
+        format!(
+            "padding: 4px 6px; text-align: center; white-space: nowrap; border: 1px solid #eeeeee; \
+            border-bottom: 3px solid {}; background-color: {};{}",
+            existential_color(
+                dependency
+                    .existential_dependency
+                    .as_ref()
+                    .map(|dep| &dep.dependency_type)
+            ),
+            temporal_color(
+                dependency
+                    .temporal_dependency
+                    .as_ref()
+                    .map(|dep| &dep.dependency_type)
+            ),
+            if highlighted { " box-shadow: inset 0 0 0 100px rgba(0, 0, 0, 0.12);" } else { "" }
+        )
+    };
+
+    html! {
+        <div class="matrix-table" style="overflow: auto; max-height: 70vh; border: 1px solid #cccccc;">
+            <table
+                onmouseover={onmouseover}
+                onmouseleave={onmouseleave}
+                style="border-collapse: separate; border-spacing: 0; font-family: monospace; font-size: 13px;"
+            >
+                <thead>
+                    <tr>
+                        <th style={format!("{} top: 0; left: 0; z-index: 3;", header_style(false))}>{"from \\ to"}</th>
+                        {for matrix.activities().iter().enumerate().map(|(column, activity)| html! {
+                            <th
+                                data-column={column.to_string()}
+                                title={activity.clone()}
+                                style={format!("{} top: 0; z-index: 2;", header_style(hovered_column == Some(column)))}
+                            >
+                                {activity}
+                            </th>
+                        })}
+                    </tr>
+                </thead>
+                <tbody>
+                    {for matrix.rows().enumerate().map(|(row, (from, dependencies))| html! {
+                        <tr>
+                            <th
+                                data-row={row.to_string()}
+                                title={from.to_string()}
+                                style={format!("{} left: 0; z-index: 1; text-align: left;", header_style(hovered_row == Some(row)))}
+                            >
+                                {from}
+                            </th>
+                            {for dependencies.iter().enumerate().map(|(column, dependency)| {
+                                let highlighted = hovered_row == Some(row) || hovered_column == Some(column);
+                                let text = if props.show_scores {
+                                    format!("{:#}", dependency)
+                                } else {
+                                    dependency.to_string()
+                                };
+                                html! {
+                                    <td
+                                        data-row={row.to_string()}
+                                        data-column={column.to_string()}
+                                        title={dependency_details(dependency)}
+                                        style={cell_style(dependency, highlighted)}
+                                    >
+                                        {text}
+                                    </td>
+                                }
+                            })}
+                        </tr>
+                    })}
+                </tbody>
+            </table>
+        </div>
+    }
+}
+
+/// Explains the colours of a `MatrixTable`.
+#[function_component(MatrixLegend)]
+pub fn matrix_legend() -> Html {
+    let swatch = |style: String, label: &str| {
+        html! {
+            <span style="margin-right: 12px; white-space: nowrap;">
+                <span style={format!("display: inline-block; width: 14px; height: 14px; margin-right: 4px; vertical-align: middle; {}", style)} />
+                {label}
+            </span>
+        }
+    };
+    let temporal = |dependency_type: temporal::DependencyType, label: &str| {
+        let color = temporal_color(Some(&dependency_type));
+        swatch(
+            format!("background-color: {}; border: 1px solid #cccccc;", color),
+            label,
+        )
+    };
+    let existential = |dependency_type: existential::DependencyType, label: &str| {
+        let color = existential_color(Some(&dependency_type));
+        swatch(format!("border-bottom: 3px solid {};", color), label)
+    };
+
+    html! {
+        <div style="display: flex; flex-wrap: wrap; padding: 5px 0; font-size: 13px;">
+            {temporal(temporal::DependencyType::Direct, "direct (≺d)")}
+            {temporal(temporal::DependencyType::Eventual, "eventual (≺)")}
+            {existential(existential::DependencyType::Implication, "implication (⇒)")}
+            {existential(existential::DependencyType::Equivalence, "equivalence (⇔)")}
+            {existential(existential::DependencyType::NegatedEquivalence, "negated equivalence (⇎)")}
+            {existential(existential::DependencyType::Nand, "nand (⊼)")}
+            {existential(existential::DependencyType::Or, "or (∨)")}
+            {existential(existential::DependencyType::Always, "in every trace (∀)")}
+            {existential(existential::DependencyType::Sometimes, "in some traces (∃)")}
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_props_compare_scores() {
+        let props = |traces: &[&[&str]]| MatrixTableProps {
+            matrix: crate::generate_adj_matrix_from_traces(
+                traces
+                    .iter()
+                    .map(|trace| trace.iter().map(|a| a.to_string()).collect())
+                    .collect(),
+                0.9,
+                0.8,
+                0.7,
+            ),
+            show_scores: false,
+        };
+        let once = props(&[&["A", "B"]]);
+        let twice = props(&[&["A", "B"], &["A", "B"]]);
+
+        // the same dependencies, only their support differs
+        assert_eq!(once.matrix, twice.matrix);
+        assert!(once != twice);
+        assert!(once == props(&[&["A", "B"]]));
+    }
+
+    #[test]
+    fn test_dependency_details() {
+        let dependency = Dependency::new(
+            "A".to_string(),
+            "B".to_string(),
+            Some(
+                temporal::TemporalDependency::new(
+                    "A",
+                    "B",
+                    temporal::DependencyType::Direct,
+                    temporal::Direction::Forward,
+                )
+                .with_scores(4, 0.75),
+            ),
+            None,
+        );
+        assert_eq!(
+            dependency_details(&dependency),
+            "A → B\n\
+            Temporal: ≺d (Direct, Forward), support 4, confidence 0.75\n\
+            Existential: none"
+        );
+    }
+}
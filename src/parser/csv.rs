@@ -0,0 +1,348 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use process_mining::{
+    event_log::{Attribute, AttributeValue, Event, Trace},
+    EventLog,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::{ParseError, XesLog, ACTIVITY_KEY, TIMESTAMP_KEY};
+
+/// Delimiters recognized by `CsvOptions::detect`, in order of preference.
+pub const DELIMITERS: [char; 4] = [',', ';', '\t', '|'];
+
+/// Formats tried after RFC 3339 for timestamps without a `CsvOptions::timestamp_format`.
+const TIMESTAMP_FORMATS: [&str; 4] = [
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y/%m/%d %H:%M:%S%.f",
+    "%d.%m.%Y %H:%M:%S%.f",
+];
+
+/// Column names recognized by `CsvOptions::detect`, compared case-insensitively.
+const CASE_COLUMNS: [&str; 5] = ["case:concept:name", "case_id", "case id", "caseid", "case"];
+const ACTIVITY_COLUMNS: [&str; 4] = ["concept:name", "activity", "activity_name", "event"];
+const TIMESTAMP_COLUMNS: [&str; 4] = ["time:timestamp", "timestamp", "time", "date"];
+
+/// Column mapping and format of a CSV event log with one event per row.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CsvOptions {
+    pub delimiter: char,
+    /// Column of the case id, the rows of a case form a trace
+    pub case_column: String,
+    /// Column of the activity name
+    pub activity_column: String,
+    /// Column of the timestamp of the events, if any
+    pub timestamp_column: Option<String>,
+    /// `chrono` format of the timestamps, e.g. `%d.%m.%Y %H:%M`. Without a format, RFC 3339 and
+    /// the ISO-like formats of `TIMESTAMP_FORMATS` are recognized.
+    pub timestamp_format: Option<String>,
+}
+
+impl CsvOptions {
+    /// Guesses the columns of a CSV log, and its delimiter if none is given, from its header
+    /// line. The case and activity fall back to the first and second column.
+    pub fn detect(bytes: &[u8], delimiter: Option<char>) -> Result<CsvOptions, ParseError> {
+        let first_line = text(bytes)?.lines().next().unwrap_or_default();
+        let delimiter = delimiter.unwrap_or_else(|| {
+            DELIMITERS
+                .into_iter()
+                .max_by_key(|delimiter| {
+                    // prefer earlier delimiters on equal counts
+                    let preference =
+                        DELIMITERS.len() - DELIMITERS.iter().position(|d| d == delimiter).unwrap();
+                    (first_line.matches(*delimiter).count(), preference)
+                })
+                .unwrap()
+        });
+        let header = csv_header(bytes, delimiter)?;
+
+        let find = |names: &[&str]| {
+            names.iter().find_map(|name| {
+                header
+                    .iter()
+                    .find(|column| column.trim().eq_ignore_ascii_case(name))
+                    .cloned()
+            })
+        };
+        let nth = |n: usize| header.get(n).or(header.last()).cloned().unwrap_or_default();
+
+        Ok(CsvOptions {
+            delimiter,
+            case_column: find(&CASE_COLUMNS).unwrap_or_else(|| nth(0)),
+            activity_column: find(&ACTIVITY_COLUMNS).unwrap_or_else(|| nth(1)),
+            timestamp_column: find(&TIMESTAMP_COLUMNS),
+            timestamp_format: None,
+        })
+    }
+}
+
+/// The column names in the header line of a CSV log.
+pub fn csv_header(bytes: &[u8], delimiter: char) -> Result<Vec<String>, ParseError> {
+    Ok(records(bytes, delimiter)?
+        .next()
+        .transpose()?
+        .map(|(_, header)| header)
+        .unwrap_or_default())
+}
+
+fn text(bytes: &[u8]) -> Result<&str, ParseError> {
+    let text = std::str::from_utf8(bytes)
+        .map_err(|error| std::io::Error::new(std::io::ErrorKind::InvalidData, error))?;
+    Ok(text.strip_prefix('\u{feff}').unwrap_or(text))
+}
+
+/// The non-blank records of a CSV log with the line they start on. Quoted fields may contain
+/// the delimiter, doubled quotes and line breaks.
+fn records(
+    bytes: &[u8],
+    delimiter: char,
+) -> Result<impl Iterator<Item = Result<(usize, Vec<String>), ParseError>> + '_, ParseError> {
+    let delimiter = u8::try_from(delimiter)
+        .ok()
+        .filter(u8::is_ascii)
+        .ok_or(ParseError::InvalidDelimiter(delimiter))?;
+    let reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text(bytes)?.as_bytes());
+    Ok(reader
+        .into_records()
+        .filter(|record| {
+            !matches!(record, Ok(record) if record.iter().all(|field| field.trim().is_empty()))
+        })
+        .map(|record| {
+            let record = record.map_err(|error| ParseError::Csv(error.to_string()))?;
+            let line = record.position().map_or(0, |position| position.line() as usize);
+            Ok((line, record.iter().map(str::to_string).collect()))
+        }))
+}
+
+fn parse_timestamp(value: &str, format: Option<&str>) -> Option<DateTime<Utc>> {
+    let naive = |format| {
+        NaiveDateTime::parse_from_str(value, format)
+            .ok()
+            .map(|date| date.and_utc())
+    };
+    match format {
+        Some(format) => DateTime::parse_from_str(value, format)
+            .ok()
+            .map(|date| date.to_utc())
+            .or_else(|| naive(format)),
+        None => DateTime::parse_from_rfc3339(value)
+            .ok()
+            .map(|date| date.to_utc())
+            .or_else(|| TIMESTAMP_FORMATS.into_iter().find_map(naive)),
+    }
+}
+
+impl XesLog {
+    /// Imports a CSV log with a header line and one event per row, in the order of the rows.
+    ///
+    /// Every column becomes an event attribute named after its header, except the activity and
+    /// timestamp columns, which become `concept:name` and `time:timestamp`. The case id becomes
+    /// the `concept:name` of the trace. Empty fields are left out.
+    pub fn from_csv(bytes: &[u8], options: &CsvOptions) -> Result<XesLog, ParseError> {
+        let mut records = records(bytes, options.delimiter)?;
+        let (_, header) = records.next().ok_or(ParseError::EmptyLog)??;
+
+        let column = |name: &str| {
+            header
+                .iter()
+                .position(|column| column == name)
+                .ok_or_else(|| ParseError::UnknownColumn(name.to_string()))
+        };
+        let case_column = column(&options.case_column)?;
+        let activity_column = column(&options.activity_column)?;
+        let timestamp_column = options
+            .timestamp_column
+            .as_deref()
+            .map(column)
+            .transpose()?;
+
+        let mut traces: Vec<Trace> = Vec::new();
+        let mut trace_of_case: HashMap<String, usize> = HashMap::new();
+
+        for record in records {
+            let (line_number, fields) = record?;
+            let case = fields
+                .get(case_column)
+                .ok_or_else(|| ParseError::MissingField {
+                    line: line_number,
+                    column: options.case_column.clone(),
+                })?;
+
+            let mut attributes = Vec::new();
+            for (column, (key, value)) in header.iter().zip(&fields).enumerate() {
+                if value.is_empty() {
+                    continue;
+                }
+                let attribute = if column == activity_column {
+                    Attribute::new(
+                        ACTIVITY_KEY.to_string(),
+                        AttributeValue::String(value.clone()),
+                    )
+                } else if Some(column) == timestamp_column {
+                    let date = parse_timestamp(value, options.timestamp_format.as_deref())
+                        .ok_or_else(|| ParseError::InvalidTimestamp {
+                            line: line_number,
+                            value: value.clone(),
+                        })?;
+                    Attribute::new(TIMESTAMP_KEY.to_string(), AttributeValue::Date(date))
+                } else if key == ACTIVITY_KEY || key == TIMESTAMP_KEY {
+                    // unmapped columns must not shadow the activity or timestamp
+                    continue;
+                } else {
+                    Attribute::new(key.clone(), AttributeValue::String(value.clone()))
+                };
+                attributes.push(attribute);
+            }
+
+            let trace = *trace_of_case.entry(case.clone()).or_insert_with(|| {
+                traces.push(Trace {
+                    attributes: vec![Attribute::new(
+                        ACTIVITY_KEY.to_string(),
+                        AttributeValue::String(case.clone()),
+                    )],
+                    events: Vec::new(),
+                });
+                traces.len() - 1
+            });
+            traces[trace].events.push(Event { attributes });
+        }
+
+        Ok(XesLog {
+            log: EventLog {
+                attributes: Vec::new(),
+                traces,
+                extensions: None,
+                classifiers: None,
+                global_trace_attrs: None,
+                global_event_attrs: None,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{ParseOptions, TimestampPolicy};
+
+    const LOG: &str = "\
+case;activity;timestamp;resource
+1;register;2024-01-01 09:00:00;alice
+2;register;2024-01-02 10:00:00;bob
+1;check;2024-01-01 08:00:00;
+1;archive;;bob
+2;check;2024-01-02 11:00:00;bob
+";
+
+    #[test]
+    fn test_detect() {
+        let options = CsvOptions::detect(LOG.as_bytes(), None).unwrap();
+        assert_eq!(
+            options,
+            CsvOptions {
+                delimiter: ';',
+                case_column: "case".to_string(),
+                activity_column: "activity".to_string(),
+                timestamp_column: Some("timestamp".to_string()),
+                timestamp_format: None,
+            }
+        );
+        assert_eq!(
+            csv_header(LOG.as_bytes(), ';').unwrap(),
+            ["case", "activity", "timestamp", "resource"]
+        );
+    }
+
+    #[test]
+    fn test_from_csv() {
+        let options = CsvOptions::detect(LOG.as_bytes(), None).unwrap();
+        let log = XesLog::from_csv(LOG.as_bytes(), &options).unwrap();
+
+        // `archive` has no timestamp and stays after `check`, which precedes it in the file
+        assert_eq!(
+            log.traces().unwrap(),
+            [
+                vec!["check", "archive", "register"],
+                vec!["register", "check"]
+            ]
+        );
+        let document_order = ParseOptions {
+            timestamps: TimestampPolicy::DocumentOrder,
+            ..ParseOptions::default()
+        };
+        assert_eq!(
+            log.parse(&document_order).unwrap().traces[0],
+            ["register", "check", "archive"]
+        );
+        assert_eq!(
+            log.classifiers().attribute_keys,
+            ["case", "concept:name", "resource", "time:timestamp"]
+        );
+    }
+
+    #[test]
+    fn test_from_csv_errors() {
+        let mut options = CsvOptions::detect(LOG.as_bytes(), None).unwrap();
+        options.timestamp_format = Some("%d.%m.%Y %H:%M".to_string());
+        assert!(matches!(
+            XesLog::from_csv(LOG.as_bytes(), &options),
+            Err(ParseError::InvalidTimestamp { line: 2, .. })
+        ));
+
+        options.activity_column = "task".to_string();
+        assert!(matches!(
+            XesLog::from_csv(LOG.as_bytes(), &options),
+            Err(ParseError::UnknownColumn(column)) if column == "task"
+        ));
+    }
+
+    #[test]
+    fn test_from_csv_quoted_fields() {
+        let log = "case,activity,timestamp,note\n\
+            1,\"check, then approve\",2024-01-01 09:00:00,\"first line\nsecond \"\"line\"\"\"\n\
+            1,archive,yesterday,\n";
+        let options = CsvOptions::detect(log.as_bytes(), None).unwrap();
+        assert_eq!(options.delimiter, ',');
+
+        // the invalid timestamp is reported on its own line, after the two lines of the note
+        assert!(matches!(
+            XesLog::from_csv(log.as_bytes(), &options),
+            Err(ParseError::InvalidTimestamp { line: 4, .. })
+        ));
+
+        let log = log.replace("yesterday", "2024-01-01 10:00:00");
+        let log = XesLog::from_csv(log.as_bytes(), &options).unwrap();
+        assert_eq!(
+            log.traces().unwrap(),
+            [vec!["check, then approve", "archive"]]
+        );
+        let note = log.log.traces[0].events[0]
+            .attributes
+            .iter()
+            .find(|attribute| attribute.key == "note")
+            .map(|attribute| attribute.value.clone());
+        assert_eq!(
+            note,
+            Some(AttributeValue::String(
+                "first line\nsecond \"line\"".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        let expected = "2024-03-01T12:30:00Z".parse::<DateTime<Utc>>().ok();
+        assert_eq!(parse_timestamp("2024-03-01T13:30:00+01:00", None), expected);
+        assert_eq!(parse_timestamp("2024-03-01 12:30:00.000", None), expected);
+        assert_eq!(
+            parse_timestamp("01.03.2024 12:30", Some("%d.%m.%Y %H:%M")),
+            expected
+        );
+        assert_eq!(parse_timestamp("yesterday", None), None);
+    }
+}
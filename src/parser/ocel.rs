@@ -0,0 +1,200 @@
+use process_mining::{
+    event_log::{
+        ocel::{ocel_struct::OCELAttributeValue, xml_ocel_import::import_ocel_xml_slice},
+        Attribute, AttributeValue, Event, Trace,
+    },
+    EventLog, OCEL,
+};
+use std::{collections::HashMap, path::Path};
+
+use super::{ParseError, XesLog, ACTIVITY_KEY, TIMESTAMP_KEY};
+
+/// An imported object-centric event log in the OCEL 2.0 JSON or XML format.
+#[derive(Debug, Clone)]
+pub struct OcelLog {
+    ocel: OCEL,
+}
+
+impl OcelLog {
+    /// Imports the log from the OCEL 2.0 file at `path`.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<OcelLog, ParseError> {
+        OcelLog::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Imports the log from the content of an OCEL 2.0 file, in JSON if it starts with `{` and
+    /// in XML otherwise.
+    pub fn from_bytes(bytes: &[u8]) -> Result<OcelLog, ParseError> {
+        let is_json = bytes
+            .iter()
+            .find(|byte| !byte.is_ascii_whitespace())
+            .is_some_and(|byte| *byte == b'{');
+        let ocel = if is_json {
+            serde_json::from_slice(bytes).map_err(|error| ParseError::Ocel(error.to_string()))?
+        } else {
+            import_ocel_xml_slice(bytes)
+        };
+
+        if ocel.events.is_empty() {
+            return Err(ParseError::EmptyLog);
+        }
+        Ok(OcelLog { ocel })
+    }
+
+    /// The object types of the log that events are related to, in the order they are declared.
+    pub fn object_types(&self) -> Vec<String> {
+        let object_type = self.object_type_of_ids();
+        let related: Vec<&String> = self
+            .ocel
+            .events
+            .iter()
+            .flat_map(|event| event.relationships.iter().flatten())
+            .filter_map(|relationship| object_type.get(relationship.object_id.as_str()).copied())
+            .collect();
+
+        self.ocel
+            .object_types
+            .iter()
+            .map(|object_type| &object_type.name)
+            .filter(|name| related.contains(name))
+            .cloned()
+            .collect()
+    }
+
+    fn object_type_of_ids(&self) -> HashMap<&str, &String> {
+        self.ocel
+            .objects
+            .iter()
+            .map(|object| (object.id.as_str(), &object.object_type))
+            .collect()
+    }
+
+    /// Flattens the log on `object_type`: every object of the type becomes a trace of the
+    /// events related to it, in the order of the log, with the event type as activity.
+    /// Objects without events are left out, and an event related to several objects of the
+    /// type occurs in each of their traces.
+    pub fn flatten(&self, object_type: &str) -> Result<XesLog, ParseError> {
+        if !self.ocel.object_types.iter().any(|t| t.name == object_type) {
+            return Err(ParseError::UnknownObjectType(object_type.to_string()));
+        }
+        let object_types = self.object_type_of_ids();
+
+        let mut traces: Vec<Trace> = Vec::new();
+        let mut trace_of_object: HashMap<&str, usize> = HashMap::new();
+
+        for event in &self.ocel.events {
+            let mut attributes = vec![
+                Attribute::new(
+                    ACTIVITY_KEY.to_string(),
+                    AttributeValue::String(event.event_type.clone()),
+                ),
+                Attribute::new(TIMESTAMP_KEY.to_string(), AttributeValue::Date(event.time)),
+            ];
+            attributes.extend(event.attributes.iter().filter_map(|attribute| {
+                let value = match &attribute.value {
+                    OCELAttributeValue::Time(value) => AttributeValue::Date(*value),
+                    OCELAttributeValue::Integer(value) => AttributeValue::Int(*value),
+                    OCELAttributeValue::Float(value) => AttributeValue::Float(*value),
+                    OCELAttributeValue::Boolean(value) => AttributeValue::Boolean(*value),
+                    OCELAttributeValue::String(value) => AttributeValue::String(value.clone()),
+                    OCELAttributeValue::Null => return None,
+                };
+                Some(Attribute::new(attribute.name.clone(), value))
+            }));
+
+            // an event may relate to the same object with several qualifiers
+            let mut objects: Vec<&str> = Vec::new();
+            for relationship in event.relationships.iter().flatten() {
+                let object = relationship.object_id.as_str();
+                if object_types.get(object).map(|t| t.as_str()) == Some(object_type)
+                    && !objects.contains(&object)
+                {
+                    objects.push(object);
+                }
+            }
+
+            for object in objects {
+                let trace = *trace_of_object.entry(object).or_insert_with(|| {
+                    traces.push(Trace {
+                        attributes: vec![Attribute::new(
+                            ACTIVITY_KEY.to_string(),
+                            AttributeValue::String(object.to_string()),
+                        )],
+                        events: Vec::new(),
+                    });
+                    traces.len() - 1
+                });
+                traces[trace].events.push(Event {
+                    attributes: attributes.clone(),
+                });
+            }
+        }
+
+        Ok(XesLog {
+            log: EventLog {
+                attributes: Vec::new(),
+                traces,
+                extensions: None,
+                classifiers: None,
+                global_trace_attrs: None,
+                global_event_attrs: None,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flatten() {
+        let log = OcelLog::from_path("./sample-data/ocel/orders.json").unwrap();
+        assert_eq!(log.object_types(), ["order", "item"]);
+
+        let orders = log.flatten("order").unwrap();
+        assert_eq!(
+            orders.traces().unwrap(),
+            [
+                vec!["place order", "pay order", "ship"],
+                vec!["place order", "ship", "pay order"]
+            ]
+        );
+        assert_eq!(
+            orders.classifiers().attribute_keys,
+            ["amount", "concept:name", "time:timestamp"]
+        );
+
+        // `i1` and `i2` are placed, picked and shipped together
+        let items = log.flatten("item").unwrap();
+        assert_eq!(
+            items.traces().unwrap(),
+            [
+                vec!["place order", "pick item", "ship"],
+                vec!["place order", "pick item", "ship"],
+                vec!["place order", "pick item", "ship"]
+            ]
+        );
+
+        assert!(matches!(
+            log.flatten("customer"),
+            Err(ParseError::UnknownObjectType(object_type)) if object_type == "customer"
+        ));
+        assert!(matches!(
+            OcelLog::from_bytes(b"{\"objectTypes\": []}"),
+            Err(ParseError::Ocel(_))
+        ));
+    }
+
+    #[test]
+    fn test_xml() {
+        let json = OcelLog::from_path("./sample-data/ocel/orders.json").unwrap();
+        let xml = OcelLog::from_path("./sample-data/ocel/orders.xmlocel").unwrap();
+        assert_eq!(xml.object_types(), json.object_types());
+        for object_type in json.object_types() {
+            assert_eq!(
+                xml.flatten(&object_type).unwrap().traces().unwrap(),
+                json.flatten(&object_type).unwrap().traces().unwrap()
+            );
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+
+use crate::parser::variants_of_traces;
+
+/// The activities of a log interned to consecutive ids, in order of their first occurrence.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Activities<'a> {
+    names: Vec<&'a str>,
+    ids: HashMap<&'a str, usize>,
+}
+
+impl<'a> Activities<'a> {
+    /// The id of `activity`, assigning the next free id to new activities.
+    pub fn intern(&mut self, activity: &'a str) -> usize {
+        *self.ids.entry(activity).or_insert_with(|| {
+            self.names.push(activity);
+            self.names.len() - 1
+        })
+    }
+
+    pub fn id(&self, activity: &str) -> Option<usize> {
+        self.ids.get(activity).copied()
+    }
+
+    pub fn name(&self, id: usize) -> &'a str {
+        self.names[id]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+}
+
+/// A set of activity ids stored as a bitset.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ActivitySet {
+    words: Vec<u64>,
+}
+
+impl ActivitySet {
+    pub fn insert(&mut self, id: usize) {
+        let word = id / 64;
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        self.words[word] |= 1 << (id % 64);
+    }
+
+    pub fn contains(&self, id: usize) -> bool {
+        self.words
+            .get(id / 64)
+            .is_some_and(|word| word & (1 << (id % 64)) != 0)
+    }
+
+    /// The ids in the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(index, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                (word != 0).then(|| {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    index * 64 + bit
+                })
+            })
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.words
+            .iter()
+            .map(|word| word.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|word| *word == 0)
+    }
+}
+
+/// A distinct sequence of activities of a log, the number of traces following it, the set of
+/// its activities and the positions of each of them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Variant<'a> {
+    pub trace: Vec<&'a str>,
+    pub count: usize,
+    pub presence: ActivitySet,
+    positions: HashMap<&'a str, Vec<usize>>,
+}
+
+impl<'a> Variant<'a> {
+    /// Indexes `trace`, interning its activities into `activities`.
+    pub fn new(trace: Vec<&'a str>, count: usize, activities: &mut Activities<'a>) -> Self {
+        let mut presence = ActivitySet::default();
+        let mut positions: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (position, activity) in trace.iter().enumerate() {
+            presence.insert(activities.intern(activity));
+            positions.entry(activity).or_default().push(position);
+        }
+        Variant {
+            trace,
+            count,
+            presence,
+            positions,
+        }
+    }
+
+    /// The positions of `activity` in the variant, in ascending order.
+    pub fn positions(&self, activity: &str) -> &[usize] {
+        self.positions
+            .get(activity)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    pub fn contains(&self, activity: &str) -> bool {
+        self.positions.contains_key(activity)
+    }
+}
+
+/// The traces of a log grouped into weighted variants, so that the dependencies of every pair
+/// of activities are checked once per variant instead of once per trace.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Variants<'a> {
+    variants: Vec<Variant<'a>>,
+    activities: Activities<'a>,
+    traces: usize,
+}
+
+impl<'a> Variants<'a> {
+    /// Groups `traces` into variants, ordered by their first trace.
+    pub fn from_traces(traces: &[Vec<&'a str>]) -> Self {
+        let mut counts = variants_of_traces(traces.to_vec());
+        let mut variants = Vec::with_capacity(counts.len());
+        let mut activities = Activities::default();
+        for trace in traces {
+            // a variant is taken out of the counts at its first trace
+            if let Some(count) = counts.remove(trace) {
+                variants.push(Variant::new(trace.clone(), count, &mut activities));
+            }
+        }
+        Variants {
+            variants,
+            activities,
+            traces: traces.len(),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Variant<'a>> {
+        self.variants.iter()
+    }
+
+    /// The activities of all variants, with the ids of their `presence` sets.
+    pub fn activities(&self) -> &Activities<'a> {
+        &self.activities
+    }
+
+    /// Number of variants.
+    pub fn len(&self) -> usize {
+        self.variants.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.variants.is_empty()
+    }
+
+    /// Number of traces of all variants.
+    pub fn traces(&self) -> usize {
+        self.traces
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variants() {
+        let traces = vec![
+            vec!["A", "B", "A"],
+            vec!["B", "C"],
+            vec!["A", "B", "A"],
+            vec!["A", "B", "A"],
+        ];
+        let variants = Variants::from_traces(&traces);
+        assert_eq!(variants.len(), 2);
+        assert_eq!(variants.traces(), 4);
+
+        let counts: Vec<(&[&str], usize)> = variants
+            .iter()
+            .map(|variant| (variant.trace.as_slice(), variant.count))
+            .collect();
+        assert_eq!(counts, [(&["A", "B", "A"][..], 3), (&["B", "C"][..], 1)]);
+
+        let variant = variants.iter().next().unwrap();
+        assert_eq!(variant.positions("A"), [0, 2]);
+        assert!(variant.positions("C").is_empty());
+        assert!(!variant.contains("C"));
+
+        let activities = variants.activities();
+        assert_eq!(activities.id("C"), Some(2));
+        assert_eq!(activities.name(1), "B");
+        assert_eq!(variant.presence.iter().collect::<Vec<_>>(), [0, 1]);
+    }
+
+    #[test]
+    fn test_activity_set() {
+        let mut set = ActivitySet::default();
+        for id in [130, 3, 64, 3] {
+            set.insert(id);
+        }
+        assert_eq!(set.iter().collect::<Vec<_>>(), [3, 64, 130]);
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(64));
+        assert!(!set.contains(63));
+        assert!(!set.contains(1000));
+        assert!(ActivitySet::default().is_empty());
+    }
+}
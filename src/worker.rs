@@ -0,0 +1,392 @@
+use gloo_timers::future::TimeoutFuture;
+use serde::{Deserialize, Serialize};
+use std::{cell::Cell, collections::HashSet, rc::Rc};
+use yew_agent::{Agent, AgentLink, HandlerId, Public};
+
+use crate::{
+    matrix::AdjacencyMatrix,
+    parser::{
+        csv::CsvOptions, ocel::OcelLog, AvailableClassifiers, ParseError, ParseOptions,
+        ParseReport, XesLog,
+    },
+    MatrixRows,
+};
+
+/// Milliseconds the worker computes before it reports its progress and handles new inputs.
+const STEP_MILLIS: f64 = 50.0;
+
+/// The content of an uploaded event log and its format.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum LogSource {
+    /// An XES log, which may be gzip compressed
+    Xes(Vec<u8>),
+    /// A CSV log with the mapping of its columns
+    Csv(Vec<u8>, CsvOptions),
+    /// An OCEL 2.0 log in JSON or XML
+    Ocel(Vec<u8>),
+}
+
+/// How the traces of a log are parsed and the thresholds to discover their matrix with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscoveryOptions {
+    pub parse: ParseOptions,
+    /// Object type an OCEL log is flattened on, every object type if `None`
+    pub object_type: Option<String>,
+    pub existential_threshold: f64,
+    pub temporal_threshold: f64,
+    pub direct_threshold: f64,
+}
+
+/// A request to the worker. A new request cancels the discovery running before it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DiscoveryInput {
+    /// Imports a log, replacing the log imported before, and discovers its matrices.
+    Import {
+        id: usize,
+        source: LogSource,
+        options: DiscoveryOptions,
+    },
+    /// Discovers the matrices of the imported log again with other options.
+    Discover {
+        id: usize,
+        options: DiscoveryOptions,
+    },
+    /// Stops the running discovery. An import in progress is finished first.
+    Cancel,
+}
+
+/// A response of the worker to the request `id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DiscoveryOutput {
+    /// The log is ready to be parsed, with the classifiers it can be parsed with and the
+    /// object types of an OCEL log.
+    Loaded {
+        id: usize,
+        classifiers: AvailableClassifiers,
+        object_types: Vec<String>,
+    },
+    Progress {
+        id: usize,
+        progress: DiscoveryProgress,
+    },
+    /// The matrices of the log, one per object type of an OCEL log.
+    Discovered {
+        id: usize,
+        matrices: Vec<DiscoveredMatrix>,
+    },
+    /// The log could not be imported or flattened.
+    Failed { id: usize, error: String },
+}
+
+/// The stage of a running discovery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DiscoveryProgress {
+    Importing,
+    /// `traces` of the `total` traces of all logs are parsed
+    Parsing {
+        traces: usize,
+        total: usize,
+    },
+    /// The dependencies of `pairs` of the `total` pairs of activities of all logs are checked
+    Discovering {
+        pairs: usize,
+        total: usize,
+    },
+}
+
+/// The matrix of a log, or the error parsing the log failed with.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscoveredMatrix {
+    /// Object type an OCEL log was flattened on
+    pub object_type: Option<String>,
+    pub result: Result<(AdjacencyMatrix, ParseReport), String>,
+}
+
+/// The logs to discover a matrix of, with the object type an OCEL log was flattened on.
+type Logs = Vec<(Option<String>, Rc<XesLog>)>;
+
+/// An imported log, kept to discover its matrices again with other options.
+#[derive(Clone)]
+enum ImportedLog {
+    Xes(Rc<XesLog>),
+    Ocel(Rc<OcelLog>),
+}
+
+impl ImportedLog {
+    fn import(source: LogSource) -> Result<ImportedLog, ParseError> {
+        Ok(match source {
+            LogSource::Xes(content) => ImportedLog::Xes(Rc::new(XesLog::from_bytes(&content)?)),
+            LogSource::Csv(content, options) => {
+                ImportedLog::Xes(Rc::new(XesLog::from_csv(&content, &options)?))
+            }
+            LogSource::Ocel(content) => ImportedLog::Ocel(Rc::new(OcelLog::from_bytes(&content)?)),
+        })
+    }
+
+    /// The logs to discover a matrix of, one per object type of an OCEL log.
+    fn logs(&self, object_type: &Option<String>) -> Result<Logs, ParseError> {
+        match self {
+            ImportedLog::Xes(xes) => Ok(vec![(None, xes.clone())]),
+            ImportedLog::Ocel(ocel) => {
+                let object_types = match object_type {
+                    Some(object_type) => vec![object_type.clone()],
+                    None => ocel.object_types(),
+                };
+                object_types
+                    .into_iter()
+                    .map(|object_type| {
+                        let xes = ocel.flatten(&object_type)?;
+                        Ok((Some(object_type), Rc::new(xes)))
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// A Web Worker importing logs and discovering their matrices off the UI thread of the web
+/// application.
+///
+/// The worker is built from the `worker` binary, which trunk bundles next to the application as
+/// `worker.js`. A discovery runs in steps of `STEP_MILLIS`, after each of which the worker
+/// reports its progress and handles new inputs, so a discovery can be cancelled while its log
+/// is parsed or its matrix computed. Importing a log is a single step and cannot be
+/// cancelled: a `Cancel` sent meanwhile is only handled once the log is imported, and stops
+/// its discovery after the first step.
+pub struct DiscoveryWorker {
+    link: AgentLink<Self>,
+    log: Option<ImportedLog>,
+    /// Incremented by every input, which cancels the discovery started before
+    generation: Rc<Cell<usize>>,
+}
+
+impl Agent for DiscoveryWorker {
+    type Reach = Public<Self>;
+    type Message = ();
+    type Input = DiscoveryInput;
+    type Output = DiscoveryOutput;
+
+    fn create(link: AgentLink<Self>) -> Self {
+        Self {
+            link,
+            log: None,
+            generation: Rc::new(Cell::new(0)),
+        }
+    }
+
+    fn update(&mut self, _msg: Self::Message) {}
+
+    fn handle_input(&mut self, input: Self::Input, handler: HandlerId) {
+        self.generation.set(self.generation.get() + 1);
+        let (id, options) = match input {
+            DiscoveryInput::Import {
+                id,
+                source,
+                options,
+            } => {
+                let progress = DiscoveryProgress::Importing;
+                self.link
+                    .respond(handler, DiscoveryOutput::Progress { id, progress });
+                match ImportedLog::import(source) {
+                    Ok(log) => self.log = Some(log),
+                    Err(e) => {
+                        self.log = None;
+                        let error = e.to_string();
+                        self.link
+                            .respond(handler, DiscoveryOutput::Failed { id, error });
+                        return;
+                    }
+                }
+                (id, options)
+            }
+            DiscoveryInput::Discover { id, options } => (id, options),
+            DiscoveryInput::Cancel => return,
+        };
+
+        let Some(log) = &self.log else {
+            let error = "No log is imported".to_string();
+            self.link
+                .respond(handler, DiscoveryOutput::Failed { id, error });
+            return;
+        };
+        let logs = match log.logs(&options.object_type) {
+            Ok(logs) => logs,
+            Err(e) => {
+                let error = e.to_string();
+                self.link
+                    .respond(handler, DiscoveryOutput::Failed { id, error });
+                return;
+            }
+        };
+
+        let mut classifiers = AvailableClassifiers::default();
+        for (_, xes) in &logs {
+            let available = xes.classifiers();
+            classifiers.declared.extend(available.declared);
+            classifiers.attribute_keys.extend(available.attribute_keys);
+        }
+        classifiers.attribute_keys.sort();
+        classifiers.attribute_keys.dedup();
+        let object_types = match log {
+            ImportedLog::Xes(_) => Vec::new(),
+            ImportedLog::Ocel(ocel) => ocel.object_types(),
+        };
+        self.link.respond(
+            handler,
+            DiscoveryOutput::Loaded {
+                id,
+                classifiers,
+                object_types,
+            },
+        );
+
+        let job = Job {
+            id,
+            handler,
+            link: self.link.clone(),
+            generation: self.generation.clone(),
+            started: self.generation.get(),
+            step_start: js_sys::Date::now(),
+        };
+        wasm_bindgen_futures::spawn_local(discover(logs, options, job));
+    }
+
+    fn name_of_resource() -> &'static str {
+        "worker.js"
+    }
+
+    fn resource_path_is_relative() -> bool {
+        true
+    }
+}
+
+/// A discovery started by the input `id` of `handler`.
+struct Job {
+    id: usize,
+    handler: HandlerId,
+    link: AgentLink<DiscoveryWorker>,
+    generation: Rc<Cell<usize>>,
+    /// Generation of the input that started the discovery
+    started: usize,
+    step_start: f64,
+}
+
+impl Job {
+    fn respond(&self, output: DiscoveryOutput) {
+        self.link.respond(self.handler, output);
+    }
+
+    /// Whether the current step took `STEP_MILLIS`, starting the next step if it did.
+    fn step_done(&mut self) -> bool {
+        let now = js_sys::Date::now();
+        let done = now - self.step_start >= STEP_MILLIS;
+        if done {
+            self.step_start = now;
+        }
+        done
+    }
+
+    /// Reports `progress` and lets the worker handle new inputs. Returns whether the discovery
+    /// goes on, `false` once it was cancelled by a new input.
+    async fn next_step(&mut self, progress: DiscoveryProgress) -> bool {
+        self.respond(DiscoveryOutput::Progress {
+            id: self.id,
+            progress,
+        });
+        TimeoutFuture::new(0).await;
+        self.step_start = js_sys::Date::now();
+        self.generation.get() == self.started
+    }
+}
+
+/// Parses `logs` and discovers their matrices, in steps which end when the `job` is
+/// cancelled.
+async fn discover(logs: Logs, options: DiscoveryOptions, mut job: Job) {
+    let total = logs.iter().map(|(_, xes)| xes.len()).sum();
+    let mut traces = 0;
+    let mut parsed = Vec::with_capacity(logs.len());
+    for (object_type, xes) in logs {
+        let result = match xes.trace_parser(&options.parse) {
+            Ok(mut parser) => loop {
+                match parser.parse_trace() {
+                    Ok(true) if job.step_done() => {
+                        let progress = DiscoveryProgress::Parsing {
+                            traces: traces + parser.parsed(),
+                            total,
+                        };
+                        if !job.next_step(progress).await {
+                            return;
+                        }
+                    }
+                    Ok(true) => {}
+                    Ok(false) => break parser.finish(),
+                    Err(e) => break Err(e),
+                }
+            },
+            Err(e) => Err(e),
+        }
+        .map(|log| {
+            let activities: HashSet<String> = log.traces.iter().flatten().cloned().collect();
+            (log, activities)
+        });
+        traces += xes.len();
+        parsed.push((object_type, result));
+        let progress = DiscoveryProgress::Parsing { traces, total };
+        if !job.next_step(progress).await {
+            return;
+        }
+    }
+
+    let total = parsed
+        .iter()
+        .filter_map(|(_, result)| result.as_ref().ok())
+        .map(|(_, activities)| activities.len() * activities.len())
+        .sum();
+    let mut pairs = 0;
+    let mut matrices = Vec::with_capacity(parsed.len());
+    for (object_type, result) in parsed {
+        let (log, activities) = match result {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                matrices.push(DiscoveredMatrix {
+                    object_type,
+                    result: Err(e.to_string()),
+                });
+                continue;
+            }
+        };
+        let traces: Vec<Vec<&str>> = log
+            .traces
+            .iter()
+            .map(|trace| trace.iter().map(String::as_str).collect())
+            .collect();
+        let matrix_rows = MatrixRows::new(
+            &activities,
+            &traces,
+            options.existential_threshold,
+            options.temporal_threshold,
+            options.direct_threshold,
+        );
+
+        let mut rows = Vec::with_capacity(activities.len());
+        for from in matrix_rows.activities() {
+            rows.push(matrix_rows.row(from));
+            pairs += activities.len();
+            if job.step_done() {
+                let progress = DiscoveryProgress::Discovering { pairs, total };
+                if !job.next_step(progress).await {
+                    return;
+                }
+            }
+        }
+        matrices.push(DiscoveredMatrix {
+            object_type,
+            result: Ok((matrix_rows.into_matrix(rows), log.report)),
+        });
+    }
+
+    job.respond(DiscoveryOutput::Discovered {
+        id: job.id,
+        matrices,
+    });
+}